@@ -330,17 +330,19 @@ function initializeUI() {
         networkInfo = { chainId };
       }
 
-      // Convert chain ID to decimal format
-      const chainIdHex = networkInfo.chainId || "0x0";
+      // The WebAssembly result carries the hex chain ID as chainIdHex, the JS fallback as chainId
+      const chainIdHex = networkInfo.chainIdHex || networkInfo.chainId || "0x0";
       let chainIdDec = parseInt(chainIdHex, 16).toString();
 
       // Get network name
-      let networkName = "Unknown Network";
-      try {
-        const networkData = await ethUtilsModule.getNetworkInfo();
-        networkName = networkData.networkName;
-      } catch (err) {
-        console.error("Error getting network name:", err);
+      let networkName = networkInfo.networkName || "Unknown Network";
+      if (!networkInfo.networkName) {
+        try {
+          const networkData = await ethUtilsModule.getNetworkInfo();
+          networkName = networkData.networkName;
+        } catch (err) {
+          console.error("Error getting network name:", err);
+        }
       }
 
       resultDiv.innerHTML = `
@@ -61,22 +61,17 @@ impl SmartContract {
         self.abi.clone()
     }
 
-    // Create ABI encoding for function call (simple simulation)
+    // Create ABI encoding for function call by delegating to `Contract`, which new code
+    // should use directly. Arguments are an array, or its JSON text for compatibility.
     pub fn encode_function_call(&self, function_name: &str, args: &JsValue) -> Result<String, JsValue> {
-        // In a real implementation, we would parse the ABI, use the function signature and arguments
-        // to create an ABI encoding. Here we're just doing a simple simulation.
+        let args = match args.as_string() {
+            Some(args_json) => js_sys::JSON::parse(&args_json)
+                .map_err(|_| JsValue::from_str(&format!("Invalid arguments JSON: {}", args_json)))?,
+            None => args.clone(),
+        };
         
-        let args_str = args.as_string().unwrap_or_else(|| "[]".to_string());
-        
-        // Function selector (first 4 bytes) simulation
-        let mut function_selector = format!("0x{:08x}", function_name.as_bytes().iter().fold(0u32, |acc, b| acc.wrapping_add(*b as u32)));
-        
-        // Represent arguments simply (not an actual encoding)
-        if args_str != "[]" {
-            function_selector.push_str(&format!("{}", args_str.replace([' ', '"', '[', ']'], "")));
-        }
-        
-        Ok(function_selector)
+        let contract = crate::contract::Contract::new(self.address.clone(), self.abi.clone())?;
+        contract.encode_function_call(function_name, args)
     }
 }
 
@@ -146,3 +141,10 @@ impl EventOptions {
         self.topics.clear();
     }
 }
+
+impl EventOptions {
+    /// Returns the topics added to these options
+    pub(crate) fn topics(&self) -> &[String] {
+        &self.topics
+    }
+}
@@ -1,4 +1,10 @@
+use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::contract::{Event, EventParameter, Function, Parameter, StateMutability};
+use super::events::ParamType;
+use super::{canonical_type, compute_function_selector, normalize_hex_digits};
+use std::cell::RefCell;
+use std::collections::HashMap;
 
 /// ABI item representing a function, event, or other contract element.
 #[derive(Serialize, Deserialize, Clone)]
@@ -7,6 +13,7 @@ pub struct AbiItem {
     pub name: Option<String>,
     pub inputs: Option<Vec<AbiInput>>,
     pub outputs: Option<Vec<AbiOutput>>,
+    #[serde(rename = "stateMutability", alias = "state_mutability")]
     pub state_mutability: Option<String>,
     pub anonymous: Option<bool>,
     pub constant: Option<bool>,
@@ -38,9 +45,30 @@ pub struct AbiComponent {
     pub components: Option<Vec<AbiComponent>>,
 }
 
-/// Parse a JSON ABI string into a vector of AbiItems.
+/// Parse a JSON ABI string into a vector of AbiItems. Accepts a bare ABI array or a
+/// compiler artifact (Hardhat, Truffle, Foundry) holding the ABI under an `"abi"` key.
 pub fn parse_abi(abi_json: &str) -> Result<Vec<AbiItem>, serde_json::Error> {
-    serde_json::from_str(abi_json)
+    serde_json::from_str(&normalize_abi_json(abi_json)?)
+}
+
+/// Extract the ABI array JSON from a compiler artifact; a bare ABI is returned unchanged
+pub(crate) fn normalize_abi_json(abi_json: &str) -> Result<String, serde_json::Error> {
+    match serde_json::from_str::<serde_json::Value>(abi_json)? {
+        serde_json::Value::Object(mut artifact) => artifact.remove("abi")
+            .map(|abi| abi.to_string())
+            .ok_or_else(|| serde::de::Error::custom("expected an ABI array or an artifact object with an \"abi\" field")),
+        _ => Ok(abi_json.to_string()),
+    }
+}
+
+/// The `"bytecode"` of a compiler artifact, if the JSON is an artifact that has one. Both a
+/// hex string (Hardhat, Truffle) and an object with the hex under `"object"` (Foundry) are read.
+pub(crate) fn artifact_bytecode(abi_json: &str) -> Option<String> {
+    let artifact = serde_json::from_str::<serde_json::Value>(abi_json).ok()?;
+    let bytecode = artifact.get("bytecode")?;
+    bytecode.as_str()
+        .or_else(|| bytecode.get("object")?.as_str())
+        .map(|bytecode| bytecode.to_string())
 }
 
 /// Get the function signature for a given function name and input types.
@@ -97,6 +125,20 @@ pub fn is_payable(abi_item: &AbiItem) -> bool {
     }
 }
 
+/// Resolve a function's state mutability, falling back to the legacy `constant` and
+/// `payable` flags of pre-0.5 Solidity ABIs when `stateMutability` is absent.
+pub(crate) fn resolve_state_mutability(abi_item: &AbiItem) -> StateMutability {
+    match abi_item.state_mutability.as_deref() {
+        Some("view") => StateMutability::View,
+        Some("pure") => StateMutability::Pure,
+        Some("payable") => StateMutability::Payable,
+        Some(_) => StateMutability::Nonpayable,
+        None if abi_item.constant == Some(true) => StateMutability::View,
+        None if abi_item.payable == Some(true) => StateMutability::Payable,
+        None => StateMutability::Nonpayable,
+    }
+}
+
 /// A simplified list of Ethereum value types.
 pub enum EthereumType {
     Address,
@@ -166,4 +208,354 @@ pub fn parse_type(type_str: &str) -> Option<EthereumType> {
     } else {
         None
     }
-} 
\ No newline at end of file
+}
+
+/// A parsed human-readable function signature as returned to JS.
+#[derive(Serialize)]
+struct SignatureFragment {
+    name: String,
+    inputs: Vec<SignatureInput>,
+    selector: String,
+}
+
+#[derive(Serialize)]
+struct SignatureInput {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    name: String,
+    r#type: String,
+}
+
+/// Parse a human-readable function signature such as `"transfer(address,uint256)"` into
+/// `{ name, inputs: [{ type }], selector }`. Parameter names are optional, tuples are written
+/// as `(address,uint256)` and may be nested or used as arrays, e.g. `(address,bytes)[]`.
+/// Input types are reported in canonical form, so `uint` becomes `uint256`.
+#[wasm_bindgen]
+pub fn parse_signature(sig: &str) -> Result<JsValue, JsValue> {
+    let (name, inputs) = parse_function_signature(sig).map_err(|e| JsValue::from_str(&e))?;
+    
+    let fragment = SignatureFragment {
+        selector: compute_function_selector(&name, &inputs),
+        inputs: inputs.iter()
+            .map(|input| SignatureInput {
+                name: input.name.clone(),
+                r#type: canonical_type(&input.r#type, input.components.as_deref()),
+            })
+            .collect(),
+        name,
+    };
+    
+    serde_wasm_bindgen::to_value(&fragment)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Parse `name(type1,type2 name2,...)` into the function name and its parameters.
+/// Tuple parameters are returned JSON-ABI style, as `tuple` types with components.
+pub fn parse_function_signature(sig: &str) -> Result<(String, Vec<Parameter>), String> {
+    let function = parse_function_fragment(sig)?;
+    Ok((function.name, function.inputs))
+}
+
+/// Common function signatures known to `known_function_name` without registering them
+const KNOWN_SIGNATURES: &[&str] = &[
+    "transfer(address,uint256)",
+    "approve(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "balanceOf(address)",
+    "allowance(address,address)",
+    "totalSupply()",
+    "name()",
+    "symbol()",
+    "decimals()",
+    "ownerOf(uint256)",
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "setApprovalForAll(address,bool)",
+    "isApprovedForAll(address,address)",
+    "getApproved(uint256)",
+    "owner()",
+    "transferOwnership(address)",
+    "renounceOwnership()",
+    "deposit()",
+    "withdraw(uint256)",
+    "multicall(bytes[])",
+    "aggregate((address,bytes)[])",
+];
+
+thread_local! {
+    // Selector -> canonical signature, seeded with KNOWN_SIGNATURES on first use
+    static SIGNATURES: RefCell<HashMap<String, String>> = RefCell::new(
+        KNOWN_SIGNATURES.iter()
+            .filter_map(|sig| selector_entry(sig).ok())
+            .collect()
+    );
+}
+
+/// The `(selector, canonical signature)` pair for a function signature
+fn selector_entry(sig: &str) -> Result<(String, String), String> {
+    let (name, inputs) = parse_function_signature(sig)?;
+    let input_types: Vec<String> = inputs.iter()
+        .map(|input| canonical_type(&input.r#type, input.components.as_deref()))
+        .collect();
+    
+    Ok((compute_function_selector(&name, &inputs), get_function_signature(&name, &input_types)))
+}
+
+/// Look up the signature of a 4-byte selector (e.g. `0xa9059cbb` gives
+/// `transfer(address,uint256)`) in the table of common and registered signatures.
+/// A best-effort name for calldata whose ABI is not available.
+#[wasm_bindgen]
+pub fn known_function_name(selector: &str) -> Option<String> {
+    let digits = normalize_hex_digits(selector).ok()?;
+    if digits.len() != 8 {
+        return None;
+    }
+    
+    let selector = format!("0x{}", digits);
+    SIGNATURES.with(|signatures| signatures.borrow().get(&selector).cloned())
+}
+
+/// Add a function signature such as `"swap(uint256,address)"` to the table used by
+/// `known_function_name`, returning its selector
+#[wasm_bindgen]
+pub fn register_signature(signature: &str) -> Result<String, JsValue> {
+    let (selector, signature) = selector_entry(signature).map_err(|e| JsValue::from_str(&e))?;
+    SIGNATURES.with(|signatures| signatures.borrow_mut().insert(selector.clone(), signature));
+    Ok(selector)
+}
+
+/// An item of a human-readable ABI
+pub(crate) enum HumanReadableItem {
+    Function(Function),
+    Event(Event),
+    /// Constructors, errors, fallback and receive functions, which contracts do not expose
+    Other,
+}
+
+/// Parse one human-readable ABI fragment, e.g.
+/// `function balanceOf(address owner) view returns (uint256)` or
+/// `event Transfer(address indexed from, address indexed to, uint256 value)`.
+/// Fragments without a keyword are read as functions.
+pub(crate) fn parse_human_readable_item(fragment: &str) -> Result<HumanReadableItem, String> {
+    let fragment = fragment.trim();
+    let keyword_len = fragment.find(|c: char| c.is_whitespace() || c == '(').unwrap_or(fragment.len());
+    
+    match &fragment[..keyword_len] {
+        "function" => Ok(HumanReadableItem::Function(parse_function_fragment(fragment)?)),
+        "event" => Ok(HumanReadableItem::Event(parse_event_fragment(&fragment[keyword_len..])?)),
+        "constructor" | "error" | "fallback" | "receive" => Ok(HumanReadableItem::Other),
+        _ => Ok(HumanReadableItem::Function(parse_function_fragment(fragment)?)),
+    }
+}
+
+/// Parse `[function] name(inputs) [modifiers] [returns (outputs)]`
+fn parse_function_fragment(fragment: &str) -> Result<Function, String> {
+    let fragment = fragment.trim();
+    let fragment = fragment.strip_prefix("function ").unwrap_or(fragment).trim_start();
+    
+    let (name, inputs, rest) = split_name_and_parameters(fragment)?;
+    
+    let (modifiers, outputs) = match rest.find("returns") {
+        Some(pos) => {
+            let returns = rest[pos + "returns".len()..].trim();
+            if !returns.starts_with('(') {
+                return Err(format!("Expected a parameter list after 'returns' in '{}'", fragment));
+            }
+            let close_pos = matching_paren(returns, 0)?;
+            if !returns[close_pos + 1..].trim().is_empty() {
+                return Err(format!("Unexpected text after return list: '{}'", returns[close_pos + 1..].trim()));
+            }
+            let outputs = parse_parameter_list(&returns[1..close_pos])?
+                .into_iter()
+                .map(|(param, _)| param)
+                .collect();
+            (&rest[..pos], outputs)
+        },
+        None => (rest, Vec::new()),
+    };
+    
+    let mut state_mutability = StateMutability::Nonpayable;
+    for modifier in modifiers.split_whitespace() {
+        state_mutability = match modifier {
+            "view" => StateMutability::View,
+            "pure" => StateMutability::Pure,
+            "payable" => StateMutability::Payable,
+            "nonpayable" => StateMutability::Nonpayable,
+            "external" | "public" => continue,
+            _ => return Err(format!("Unexpected '{}' in function '{}'", modifier, name)),
+        };
+    }
+    
+    Ok(Function {
+        name,
+        inputs: inputs.into_iter().map(|(param, _)| param).collect(),
+        outputs,
+        state_mutability,
+    })
+}
+
+/// Parse `name(inputs) [anonymous]`, keeping each input's `indexed` flag
+fn parse_event_fragment(fragment: &str) -> Result<Event, String> {
+    let (name, inputs, rest) = split_name_and_parameters(fragment.trim())?;
+    
+    let anonymous = match rest.trim() {
+        "" => false,
+        "anonymous" => true,
+        other => return Err(format!("Unexpected '{}' in event '{}'", other, name)),
+    };
+    
+    Ok(Event {
+        name,
+        inputs: inputs.into_iter()
+            .map(|(param, indexed)| EventParameter {
+                name: param.name,
+                r#type: param.r#type,
+                indexed,
+                components: param.components,
+            })
+            .collect(),
+        anonymous,
+    })
+}
+
+/// Split `name(params) rest` into the name, the parsed parameters and the remaining text
+fn split_name_and_parameters(fragment: &str) -> Result<(String, ParameterList, &str), String> {
+    let open_pos = fragment.find('(').ok_or_else(|| format!("Missing parameter list in '{}'", fragment))?;
+    let name = fragment[..open_pos].trim();
+    if !is_identifier(name) {
+        return Err(format!("Invalid name '{}'", name));
+    }
+    
+    let close_pos = matching_paren(fragment, open_pos)?;
+    let params = parse_parameter_list(&fragment[open_pos + 1..close_pos])?;
+    
+    Ok((name.to_string(), params, &fragment[close_pos + 1..]))
+}
+
+/// Parameters paired with their `indexed` flag
+type ParameterList = Vec<(Parameter, bool)>;
+
+/// Parse a comma-separated parameter list. Each entry is returned with its `indexed` flag.
+pub(crate) fn parse_parameter_list(list: &str) -> Result<ParameterList, String> {
+    if list.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let mut params = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or_else(|| format!("Unbalanced parentheses in '{}'", list))?,
+            ',' if depth == 0 => {
+                params.push(parse_parameter(&list[start..i])?);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    if depth != 0 {
+        return Err(format!("Unbalanced parentheses in '{}'", list));
+    }
+    params.push(parse_parameter(&list[start..])?);
+    
+    Ok(params)
+}
+
+/// Parse a standalone type string such as `uint256`, `address[]` or `(address,uint256)[]`
+pub(crate) fn parse_type_string(type_str: &str) -> Result<ParamType, String> {
+    let (param, indexed) = parse_parameter(type_str)?;
+    if indexed || !param.name.is_empty() {
+        return Err(format!("Expected a type without a name, got '{}'", type_str.trim()));
+    }
+    ParamType::from_parameter(&param).ok_or_else(|| format!("Unsupported type '{}'", type_str))
+}
+
+/// Parse a single parameter: a type followed by an optional `indexed` keyword,
+/// data location and name, e.g. `address indexed from` or `(uint256,bool)[] items`.
+fn parse_parameter(fragment: &str) -> Result<(Parameter, bool), String> {
+    let fragment = fragment.trim();
+    if fragment.is_empty() {
+        return Err("Empty parameter in parameter list".to_string());
+    }
+    
+    let tuple_start = if fragment.starts_with('(') {
+        Some(0)
+    } else if fragment.starts_with("tuple(") {
+        Some("tuple".len())
+    } else {
+        None
+    };
+    
+    let (r#type, components, rest) = match tuple_start {
+        Some(open_pos) => {
+            let close_pos = matching_paren(fragment, open_pos)?;
+            let components = parse_parameter_list(&fragment[open_pos + 1..close_pos])?
+                .into_iter()
+                .map(|(param, _)| param)
+                .collect::<Vec<_>>();
+            
+            // Array dimensions directly follow the closing parenthesis
+            let after = &fragment[close_pos + 1..];
+            let dims_len = after.find(char::is_whitespace).unwrap_or(after.len());
+            (format!("tuple{}", &after[..dims_len]), Some(components), &after[dims_len..])
+        },
+        None => {
+            let type_len = fragment.find(char::is_whitespace).unwrap_or(fragment.len());
+            (normalize_type_alias(&fragment[..type_len]), None, &fragment[type_len..])
+        },
+    };
+    
+    let mut indexed = false;
+    let mut name = String::new();
+    for word in rest.split_whitespace() {
+        match word {
+            "indexed" if !indexed && name.is_empty() => indexed = true,
+            "memory" | "calldata" | "storage" if name.is_empty() => {},
+            _ if name.is_empty() && is_identifier(word) => name = word.to_string(),
+            _ => return Err(format!("Unexpected '{}' in parameter '{}'", word, fragment)),
+        }
+    }
+    
+    let param = Parameter { name, r#type, components };
+    if ParamType::from_parameter(&param).is_none() {
+        return Err(format!("Unsupported type in parameter '{}'", fragment));
+    }
+    
+    Ok((param, indexed))
+}
+
+/// Expand the `uint`/`int` shorthands, keeping any array dimensions
+fn normalize_type_alias(type_str: &str) -> String {
+    let base_len = type_str.find('[').unwrap_or(type_str.len());
+    let (base, dims) = type_str.split_at(base_len);
+    match base {
+        "uint" => format!("uint256{}", dims),
+        "int" => format!("int256{}", dims),
+        _ => type_str.to_string(),
+    }
+}
+
+/// Find the parenthesis closing the one at `open_pos`
+fn matching_paren(text: &str, open_pos: usize) -> Result<usize, String> {
+    let mut depth = 0usize;
+    for (i, c) in text[open_pos..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(open_pos + i);
+                }
+            },
+            _ => {},
+        }
+    }
+    Err(format!("Unbalanced parentheses in '{}'", text))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
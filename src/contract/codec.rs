@@ -0,0 +1,503 @@
+use wasm_bindgen::prelude::*;
+use primitive_types::U256;
+use super::events::ParamType;
+use super::utils::{bytes_to_hex, hex_to_bytes, is_valid_address, keccak256};
+
+/// A single decoded ABI value.
+/// Values are decoded into `Token`s first so the same engine can back
+/// function results, calldata and logs, and are converted to JS at the edge.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Address(String),
+    Uint(U256),
+    /// Signed integers are kept in their 256-bit two's complement form
+    Int(U256),
+    Bool(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    FixedBytes(Vec<u8>),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    /// Converts the token to a JS value.
+    /// Integers become decimal strings, byte values become 0x-prefixed hex,
+    /// and arrays/tuples become JS arrays.
+    pub fn to_js_value(&self) -> JsValue {
+        match self {
+            Token::Address(address) => JsValue::from_str(address),
+            Token::Uint(_) | Token::Int(_) => JsValue::from_str(&self.to_decimal_string()),
+            Token::Bool(value) => JsValue::from_bool(*value),
+            Token::String(value) => JsValue::from_str(value),
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => JsValue::from_str(&bytes_to_hex(bytes)),
+            Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                let array = js_sys::Array::new();
+                for token in tokens {
+                    array.push(&token.to_js_value());
+                }
+                array.into()
+            },
+        }
+    }
+
+    /// Returns the decimal representation of an integer token.
+    /// Non-integer tokens are rendered as hex or their string value, and arrays
+    /// and tuples as `[a,b]` and `(a,b)`.
+    pub fn to_decimal_string(&self) -> String {
+        match self {
+            Token::Uint(value) => value.to_string(),
+            Token::Int(value) => {
+                if value.bit(255) {
+                    // Negative: take the two's complement magnitude
+                    let magnitude = (!*value).overflowing_add(U256::one()).0;
+                    format!("-{}", magnitude)
+                } else {
+                    value.to_string()
+                }
+            },
+            Token::Address(address) => address.clone(),
+            Token::Bool(value) => value.to_string(),
+            Token::String(value) => value.clone(),
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => bytes_to_hex(bytes),
+            Token::Array(tokens) | Token::FixedArray(tokens) => {
+                let values: Vec<String> = tokens.iter().map(|t| t.to_decimal_string()).collect();
+                format!("[{}]", values.join(","))
+            },
+            Token::Tuple(tokens) => {
+                let values: Vec<String> = tokens.iter().map(|t| t.to_decimal_string()).collect();
+                format!("({})", values.join(","))
+            },
+        }
+    }
+}
+
+impl Token {
+    /// Returns true if the token is dynamically sized in the ABI encoding
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::FixedArray(tokens) | Token::Tuple(tokens) => tokens.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+/// Converts a JS argument into a token of the given ABI type.
+/// Integers may be passed as decimal or 0x-prefixed hex strings, safe-integer numbers
+/// or BigInts; `bool` accepts booleans; byte types and addresses take hex strings;
+/// arrays take JS arrays whose elements are converted with the element type, and
+/// tuples take JS arrays with one value per component.
+pub fn tokenize(param_type: &ParamType, value: &JsValue) -> Result<Token, String> {
+    match param_type {
+        ParamType::Address => {
+            let address = expect_string(param_type, value)?;
+            if !is_valid_address(&address) {
+                return Err(format!("Invalid Ethereum address: {}", address));
+            }
+            Ok(Token::Address(address))
+        },
+        ParamType::Uint(bits) | ParamType::Int(bits) => {
+            let signed = matches!(param_type, ParamType::Int(_));
+            let (negative, magnitude) = if let Some(number) = value.as_f64() {
+                integer_from_number(number)?
+            } else if value.is_bigint() {
+                let digits = js_sys::BigInt::from(value.clone()).to_string(10)
+                    .map_err(|_| "Failed to convert BigInt to string".to_string())?;
+                parse_integer(&String::from(digits))?
+            } else if let Some(text) = value.as_string() {
+                parse_integer(&text)?
+            } else {
+                return Err(format!("Expected a number, BigInt or string for {}, got {}", param_type, js_type_name(value)));
+            };
+            integer_token(negative, magnitude, signed, *bits)
+        },
+        ParamType::Bool => {
+            if let Some(flag) = value.as_bool() {
+                return Ok(Token::Bool(flag));
+            }
+            match value.as_string().as_deref() {
+                Some("true") => Ok(Token::Bool(true)),
+                Some("false") => Ok(Token::Bool(false)),
+                _ => Err(format!("Expected a boolean for bool, got {}", js_type_name(value))),
+            }
+        },
+        ParamType::String => Ok(Token::String(expect_string(param_type, value)?)),
+        ParamType::Bytes => Ok(Token::Bytes(hex_to_bytes(&expect_string(param_type, value)?)?)),
+        ParamType::FixedBytes(size) => {
+            let bytes = hex_to_bytes(&expect_string(param_type, value)?)?;
+            if bytes.len() != *size {
+                return Err(format!("Expected {} bytes for {}, got {}", size, param_type, bytes.len()));
+            }
+            Ok(Token::FixedBytes(bytes))
+        },
+        ParamType::Array(element_type) => Ok(Token::Array(tokenize_elements(param_type, element_type, value, None)?)),
+        ParamType::FixedArray(element_type, size) => {
+            Ok(Token::FixedArray(tokenize_elements(param_type, element_type, value, Some(*size))?))
+        },
+        ParamType::Tuple(component_types) => {
+            if !js_sys::Array::is_array(value) {
+                return Err(format!("Expected an array for {}, got {}", param_type, js_type_name(value)));
+            }
+            let values = js_sys::Array::from(value).to_vec();
+            if values.len() != component_types.len() {
+                return Err(format!("Expected {} values for {}, got {}", component_types.len(), param_type, values.len()));
+            }
+            
+            let mut tokens = Vec::with_capacity(values.len());
+            for (component_type, component) in component_types.iter().zip(values.iter()) {
+                tokens.push(tokenize(component_type, component)?);
+            }
+            Ok(Token::Tuple(tokens))
+        },
+    }
+}
+
+/// Converts a JS array into tokens of the element type, checking the length of fixed-size arrays
+fn tokenize_elements(param_type: &ParamType, element_type: &ParamType, value: &JsValue, size: Option<usize>) -> Result<Vec<Token>, String> {
+    if !js_sys::Array::is_array(value) {
+        return Err(format!("Expected an array for {}, got {}", param_type, js_type_name(value)));
+    }
+    
+    let elements = js_sys::Array::from(value).to_vec();
+    if let Some(size) = size {
+        if elements.len() != size {
+            return Err(format!("Expected {} elements for {}, got {}", size, param_type, elements.len()));
+        }
+    }
+    
+    elements.iter().enumerate()
+        .map(|(i, element)| tokenize(element_type, element).map_err(|e| format!("element {}: {}", i, e)))
+        .collect()
+}
+
+fn expect_string(param_type: &ParamType, value: &JsValue) -> Result<String, String> {
+    value.as_string()
+        .ok_or_else(|| format!("Expected a string for {}, got {}", param_type, js_type_name(value)))
+}
+
+/// Returns the JS type of a value for error messages, distinguishing null and arrays
+fn js_type_name(value: &JsValue) -> String {
+    if value.is_null() {
+        "null".to_string()
+    } else if js_sys::Array::is_array(value) {
+        "array".to_string()
+    } else {
+        value.js_typeof().as_string().unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// Splits a JS number into sign and magnitude, rejecting fractions and unsafe integers
+fn integer_from_number(number: f64) -> Result<(bool, U256), String> {
+    // Number.MAX_SAFE_INTEGER; larger values have already lost precision
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    
+    if !number.is_finite() || number.fract() != 0.0 {
+        return Err(format!("Expected an integer, got {}", number));
+    }
+    if number.abs() > MAX_SAFE_INTEGER {
+        return Err(format!("Number {} is not a safe integer, pass it as a string or BigInt", number));
+    }
+    Ok((number < 0.0, U256::from(number.abs() as u64)))
+}
+
+/// Parses a decimal or 0x-prefixed hex integer string into sign and magnitude
+pub fn parse_integer(text: &str) -> Result<(bool, U256), String> {
+    let trimmed = text.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    
+    let magnitude = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex) if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            U256::from_str_radix(hex, 16).ok()
+        },
+        Some(_) => None,
+        None if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) => {
+            U256::from_dec_str(digits).ok()
+        },
+        None => None,
+    }.ok_or_else(|| format!("Invalid integer: '{}'", text))?;
+    
+    Ok((negative && !magnitude.is_zero(), magnitude))
+}
+
+/// Builds a `uintN`/`intN` token, checking the value fits in the given number of bits
+pub fn integer_token(negative: bool, magnitude: U256, signed: bool, bits: usize) -> Result<Token, String> {
+    let type_name = format!("{}{}", if signed { "int" } else { "uint" }, bits);
+    if bits == 0 {
+        return Err(format!("Unsupported type {}: integer sizes start at 8 bits", type_name));
+    }
+    let out_of_range = || {
+        let sign = if negative { "-" } else { "" };
+        format!("Value {}{} is out of range for {}", sign, magnitude, type_name)
+    };
+    
+    if !signed {
+        if negative || magnitude.bits() > bits {
+            return Err(out_of_range());
+        }
+        return Ok(Token::Uint(magnitude));
+    }
+    
+    // intN holds -2^(N-1) ..= 2^(N-1) - 1
+    let limit = U256::one() << (bits - 1);
+    if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+        return Err(out_of_range());
+    }
+    
+    if negative {
+        Ok(Token::Int((!magnitude).overflowing_add(U256::one()).0))
+    } else {
+        Ok(Token::Int(magnitude))
+    }
+}
+
+/// ABI-encodes the given tokens as a tuple
+pub fn encode(tokens: &[Token]) -> Result<Vec<u8>, String> {
+    let head_length: usize = tokens.iter()
+        .map(|t| if t.is_dynamic() { 32 } else { encoded_head_size(t) })
+        .sum();
+    
+    let mut head = Vec::with_capacity(head_length);
+    let mut tail = Vec::new();
+    
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend_from_slice(&usize_word(head_length + tail.len()));
+            tail.extend(encode_token(token)?);
+        } else {
+            head.extend(encode_token(token)?);
+        }
+    }
+    
+    head.extend(tail);
+    Ok(head)
+}
+
+/// Encodes a function call from its canonical signature, e.g. `balanceOf(address)`
+pub fn encode_function_data(signature: &str, tokens: &[Token]) -> Result<Vec<u8>, String> {
+    let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+    data.extend(encode(tokens)?);
+    Ok(data)
+}
+
+/// Decodes the reason from revert data: `Error(string)` yields the message and
+/// `Panic(uint256)` a description of the panic code. Returns None for other data.
+pub fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    
+    let (selector, payload) = data.split_at(4);
+    match selector {
+        // Error(string)
+        [0x08, 0xc3, 0x79, 0xa0] => match decode(&[ParamType::String], payload).ok()?.pop()? {
+            Token::String(reason) => Some(reason),
+            _ => None,
+        },
+        // Panic(uint256)
+        [0x4e, 0x48, 0x7b, 0x71] => {
+            let code = decode(&[ParamType::Uint(256)], payload).ok()?.pop()?;
+            match code {
+                Token::Uint(code) => Some(format!("Panic(0x{:02x}): {}", code, panic_description(code.low_u64()))),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+fn panic_description(code: u64) -> &'static str {
+    match code {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic code",
+    }
+}
+
+/// Encodes a single token in place (static) or as its tail (dynamic)
+fn encode_token(token: &Token) -> Result<Vec<u8>, String> {
+    match token {
+        Token::Address(address) => {
+            let bytes = hex_to_bytes(address)?;
+            if bytes.len() != 20 {
+                return Err(format!("Invalid Ethereum address: {}", address));
+            }
+            let mut word = vec![0u8; 12];
+            word.extend(bytes);
+            Ok(word)
+        },
+        Token::Uint(value) | Token::Int(value) => {
+            let mut word = vec![0u8; 32];
+            value.to_big_endian(&mut word);
+            Ok(word)
+        },
+        Token::Bool(value) => {
+            let mut word = vec![0u8; 32];
+            word[31] = u8::from(*value);
+            Ok(word)
+        },
+        Token::FixedBytes(bytes) => {
+            if bytes.len() > 32 {
+                return Err("Fixed bytes value longer than 32 bytes".to_string());
+            }
+            let mut word = bytes.clone();
+            word.resize(32, 0);
+            Ok(word)
+        },
+        Token::Bytes(bytes) => {
+            let mut encoded = usize_word(bytes.len()).to_vec();
+            encoded.extend(pad_right(bytes));
+            Ok(encoded)
+        },
+        Token::String(value) => {
+            let mut encoded = usize_word(value.len()).to_vec();
+            encoded.extend(pad_right(value.as_bytes()));
+            Ok(encoded)
+        },
+        Token::Array(tokens) => {
+            let mut encoded = usize_word(tokens.len()).to_vec();
+            encoded.extend(encode(tokens)?);
+            Ok(encoded)
+        },
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => encode(tokens),
+    }
+}
+
+/// Number of bytes a static token occupies in the head
+fn encoded_head_size(token: &Token) -> usize {
+    match token {
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => tokens.iter().map(encoded_head_size).sum(),
+        _ => 32,
+    }
+}
+
+fn usize_word(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+/// Right-pads bytes with zeros to a multiple of 32 bytes
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len().div_ceil(32) * 32, 0);
+    padded
+}
+
+/// Decodes ABI-encoded data according to the given list of types
+pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, String> {
+    decode_params(types, data, 0)
+}
+
+/// Decodes a tuple of values whose head starts at `base`.
+/// Offsets of dynamic values are relative to `base`.
+fn decode_params(types: &[ParamType], data: &[u8], base: usize) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::with_capacity(types.len());
+    let mut head = base;
+
+    for param_type in types {
+        if param_type.is_dynamic() {
+            let offset = read_usize(data, head)?;
+            let position = base.checked_add(offset)
+                .ok_or_else(|| "ABI offset out of range".to_string())?;
+            tokens.push(decode_token(param_type, data, position)?);
+            head += 32;
+        } else {
+            tokens.push(decode_token(param_type, data, head)?);
+            head += head_size(param_type);
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Decodes a single value located at `position`
+fn decode_token(param_type: &ParamType, data: &[u8], position: usize) -> Result<Token, String> {
+    match param_type {
+        ParamType::Address => {
+            let word = read_word(data, position)?;
+            Ok(Token::Address(bytes_to_hex(&word[12..])))
+        },
+        ParamType::Uint(_) => Ok(Token::Uint(U256::from_big_endian(read_word(data, position)?))),
+        ParamType::Int(_) => Ok(Token::Int(U256::from_big_endian(read_word(data, position)?))),
+        ParamType::Bool => {
+            let word = read_word(data, position)?;
+            Ok(Token::Bool(word.iter().any(|b| *b != 0)))
+        },
+        ParamType::FixedBytes(size) => {
+            let word = read_word(data, position)?;
+            Ok(Token::FixedBytes(word[..*size].to_vec()))
+        },
+        ParamType::Bytes => Ok(Token::Bytes(read_dynamic_bytes(data, position)?.to_vec())),
+        ParamType::String => {
+            let bytes = read_dynamic_bytes(data, position)?;
+            String::from_utf8(bytes.to_vec())
+                .map(Token::String)
+                .map_err(|_| "Invalid UTF-8 in ABI-encoded string".to_string())
+        },
+        ParamType::Array(element_type) => {
+            let length = read_usize(data, position)?;
+            // Every element occupies at least one word, which bounds the length
+            if length > data.len().saturating_sub(position + 32) / 32 {
+                return Err("ABI array length out of range".to_string());
+            }
+            let types = vec![(**element_type).clone(); length];
+            Ok(Token::Array(decode_params(&types, data, position + 32)?))
+        },
+        ParamType::FixedArray(element_type, size) => {
+            if *size > data.len().saturating_sub(position) / 32 {
+                return Err("ABI array length out of range".to_string());
+            }
+            let types = vec![(**element_type).clone(); *size];
+            Ok(Token::FixedArray(decode_params(&types, data, position)?))
+        },
+        ParamType::Tuple(types) => Ok(Token::Tuple(decode_params(types, data, position)?)),
+    }
+}
+
+/// Number of bytes a static type occupies in the head
+fn head_size(param_type: &ParamType) -> usize {
+    match param_type {
+        ParamType::FixedArray(element_type, size) if !element_type.is_dynamic() => {
+            head_size(element_type) * size
+        },
+        ParamType::Tuple(types) if !param_type.is_dynamic() => types.iter().map(head_size).sum(),
+        _ => 32,
+    }
+}
+
+fn read_word(data: &[u8], position: usize) -> Result<&[u8], String> {
+    position.checked_add(32)
+        .and_then(|end| data.get(position..end))
+        .ok_or_else(|| "ABI data too short".to_string())
+}
+
+fn read_usize(data: &[u8], position: usize) -> Result<usize, String> {
+    let word = read_word(data, position)?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return Err("ABI offset or length out of range".to_string());
+    }
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(value))
+        .map_err(|_| "ABI offset or length out of range".to_string())
+}
+
+fn read_dynamic_bytes(data: &[u8], position: usize) -> Result<&[u8], String> {
+    let length = read_usize(data, position)?;
+    let start = position + 32;
+    start.checked_add(length)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| "ABI data too short".to_string())
+}
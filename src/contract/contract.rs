@@ -32,6 +32,13 @@ pub struct Event {
     pub anonymous: bool,
 }
 
+/// Represents a custom error (`error Name(...)`) in a smart contract
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomError {
+    pub name: String,
+    pub inputs: Vec<Parameter>,
+}
+
 /// Parameter for a function
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Parameter {
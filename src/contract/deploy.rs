@@ -1,6 +1,9 @@
 use wasm_bindgen::prelude::*;
 use super::Contract;
 use crate::eth_integration::get_provider;
+use crate::eth_integration::{with_timeout, PollConfig};
+use crate::eth_integration::errors::provider_error_message;
+use crate::error::RustWasmError;
 use js_sys::{Object, Reflect, Promise, Array};
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen::JsCast;
@@ -11,7 +14,7 @@ use std::thread::sleep;
 #[cfg(target_arch = "wasm32")]
 use web_sys;
 
-use crate::contract::abi::AbiItem;
+use crate::contract::abi::{artifact_bytecode, normalize_abi_json, AbiItem};
 use crate::utils;
 
 /// Contract deployment module that handles deploying new smart contracts to the Ethereum network.
@@ -21,25 +24,24 @@ pub struct ContractDeployer {
     abi: String,
     eth_provider: JsValue,
     constructor_args: Vec<JsValue>,
+    poll_config: PollConfig,
+    gas_buffer_percent: u32,
+    /// Timeout for the deployment's provider requests; the module default when unset
+    request_timeout_ms: Option<u32>,
 }
 
 #[wasm_bindgen]
 impl ContractDeployer {
-    /// Creates a new ContractDeployer with the provided bytecode and ABI.
+    /// Creates a new ContractDeployer with the provided bytecode and ABI. The ABI may also be
+    /// a compiler artifact with `"abi"` and `"bytecode"` fields, in which case an empty
+    /// `bytecode` argument is taken from the artifact.
     #[wasm_bindgen(constructor)]
     pub fn new(bytecode: String, abi: String) -> Result<ContractDeployer, JsValue> {
+        let (bytecode, abi) = split_artifact(bytecode, abi)?;
+        
         // Get window object
         #[cfg(target_arch = "wasm32")]
-        let eth_provider = {
-            let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window object found"))?;
-            
-            // Check for ethereum provider
-            if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-                return Err(JsValue::from_str("Ethereum provider not found in window object"));
-            }
-            
-            js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?
-        };
+        let eth_provider = get_provider()?;
         
         #[cfg(not(target_arch = "wasm32"))]
         let eth_provider = JsValue::null();
@@ -49,6 +51,48 @@ impl ContractDeployer {
             abi,
             eth_provider,
             constructor_args: Vec::new(),
+            poll_config: PollConfig::default(),
+            gas_buffer_percent: utils::DEFAULT_GAS_BUFFER_PERCENT,
+            request_timeout_ms: None,
+        })
+    }
+
+    /// Creates a ContractDeployer from a Hardhat, Foundry or Truffle artifact JSON, taking its
+    /// `abi` and its `bytecode` (a hex string, or `{ object }` as Foundry writes it)
+    #[wasm_bindgen]
+    pub fn from_artifact(artifact_json: String) -> Result<ContractDeployer, JsValue> {
+        let artifact: serde_json::Value = serde_json::from_str(&artifact_json)
+            .map_err(|e| JsValue::from(RustWasmError::AbiParse(e.to_string())))?;
+        if !artifact.is_object() {
+            return Err(JsValue::from_str("Artifact must be a JSON object with \"abi\" and \"bytecode\" fields"));
+        }
+        if artifact.get("abi").is_none() {
+            return Err(JsValue::from_str("Artifact has no \"abi\" field"));
+        }
+        
+        let bytecode = artifact_bytecode(&artifact_json)
+            .ok_or_else(|| JsValue::from_str("Artifact has no \"bytecode\" field (a hex string or an object with \"object\")"))?;
+        if bytecode.trim_start_matches("0x").is_empty() {
+            return Err(JsValue::from_str("Artifact bytecode is empty; abstract contracts and interfaces cannot be deployed"));
+        }
+        
+        Self::new(bytecode, artifact_json)
+    }
+
+    /// Creates a ContractDeployer that sends its requests through the given EIP-1193 provider
+    /// instead of `window.ethereum`. Contracts it deploys use the same provider.
+    #[wasm_bindgen]
+    pub fn with_provider(bytecode: String, abi: String, provider: JsValue) -> Result<ContractDeployer, JsValue> {
+        crate::eth_integration::check_provider(&provider)?;
+        let (bytecode, abi) = split_artifact(bytecode, abi)?;
+        Ok(ContractDeployer {
+            bytecode,
+            abi,
+            eth_provider: provider,
+            constructor_args: Vec::new(),
+            poll_config: PollConfig::default(),
+            gas_buffer_percent: utils::DEFAULT_GAS_BUFFER_PERCENT,
+            request_timeout_ms: None,
         })
     }
 
@@ -59,6 +103,35 @@ impl ContractDeployer {
         Ok(())
     }
 
+    /// Set how the deployment receipt is polled for; the defaults are kept when unset
+    #[wasm_bindgen]
+    pub fn set_poll_config(&mut self, poll_config: PollConfig) {
+        self.poll_config = poll_config;
+    }
+
+    /// Set the percentage (0-100, default 20) added to the gas estimate when `deploy`
+    /// is called without a gas limit
+    #[wasm_bindgen]
+    pub fn with_gas_buffer_percent(&mut self, percent: u32) -> Result<(), JsValue> {
+        if percent > 100 {
+            return Err(JsValue::from_str(&format!("Gas buffer must be between 0 and 100 percent, got {}", percent)));
+        }
+        self.gas_buffer_percent = percent;
+        Ok(())
+    }
+
+    /// Set the timeout in milliseconds for this deployer's provider requests (gas estimate,
+    /// sending and receipt polling), overriding `set_request_timeout`; `0` waits indefinitely
+    #[wasm_bindgen]
+    pub fn set_request_timeout(&mut self, timeout_ms: u32) {
+        self.request_timeout_ms = Some(timeout_ms);
+    }
+
+    /// The request timeout in milliseconds that applies to this deployer
+    fn request_timeout(&self) -> u32 {
+        self.request_timeout_ms.unwrap_or_else(crate::eth_integration::request_timeout)
+    }
+
     /// Encode constructor arguments with the contract bytecode
     fn encode_constructor_data(&self) -> Result<String, JsValue> {
         // Parse ABI to find constructor
@@ -67,20 +140,37 @@ impl ContractDeployer {
             Err(e) => return Err(JsValue::from_str(&format!("Failed to parse ABI: {}", e))),
         };
         
-        // Find constructor in ABI
-        let constructor = abi_items.iter().find(|item| item.r#type == "constructor");
+        // Find constructor in ABI; Solidity allows at most one
+        let constructors: Vec<&AbiItem> = abi_items.iter().filter(|item| item.r#type == "constructor").collect();
+        if constructors.len() > 1 {
+            return Err(JsValue::from_str(&format!(
+                "ABI has {} constructors, but a contract can only have one", constructors.len()
+            )));
+        }
         
         // If constructor has inputs, encode them
         // For simplicity, we're just appending args as strings
         // A real implementation would use proper ABI encoding
         let mut encoded_data = self.bytecode.clone();
         
-        if let Some(constructor) = constructor {
-            if let Some(inputs) = &constructor.inputs {
+        match constructors.first() {
+            // Without a constructor in the ABI the contract has the implicit one, which takes no arguments
+            None if !self.constructor_args.is_empty() => {
+                return Err(JsValue::from_str(&format!(
+                    "ABI has no constructor, so no constructor arguments are allowed, but {} were set",
+                    self.constructor_args.len()
+                )));
+            },
+            None => {},
+            Some(constructor) => {
+                let inputs = constructor.inputs.as_deref().unwrap_or_default();
                 if inputs.len() != self.constructor_args.len() {
+                    let expected_types: Vec<&str> = inputs.iter().map(|input| input.r#type.as_str()).collect();
                     return Err(JsValue::from_str(&format!(
-                        "Expected {} constructor arguments, got {}",
-                        inputs.len(), self.constructor_args.len()
+                        "Expected {} constructor arguments ({}), got {}",
+                        inputs.len(),
+                        if expected_types.is_empty() { "none".to_string() } else { expected_types.join(", ") },
+                        self.constructor_args.len()
                     )));
                 }
                 
@@ -103,7 +193,7 @@ impl ContractDeployer {
                         return Err(JsValue::from_str("Unsupported argument type"));
                     }
                 }
-            }
+            },
         }
         
         // Ensure bytecode has 0x prefix
@@ -114,49 +204,97 @@ impl ContractDeployer {
         Ok(encoded_data)
     }
 
-    /// Estimates the gas required to deploy the contract with the given constructor arguments.
+    /// Estimates the gas required to deploy the contract with the given constructor arguments,
+    /// returned as a decimal string. When `from_address` is omitted, the first connected account
+    /// is used. `value` (wei, decimal or hex) is sent to a payable constructor; `gas` caps the
+    /// estimate, failing early when the deployment would need more, e.g. the block gas limit.
     #[wasm_bindgen]
-    pub async fn estimate_gas(&self, from_address: String) -> Result<JsValue, JsValue> {
-        let encoded_data = self.encode_constructor_data()?;
-        
-        // Create transaction object
-        let tx_obj = Object::new();
-        Reflect::set(&tx_obj, &JsValue::from_str("from"), &JsValue::from_str(&from_address))?;
-        Reflect::set(&tx_obj, &JsValue::from_str("data"), &JsValue::from_str(&encoded_data))?;
-        
-        // Call estimateGas method on provider
-        let request_obj = Object::new();
-        Reflect::set(&request_obj, &JsValue::from_str("method"), &JsValue::from_str("eth_estimateGas"))?;
-        
-        let params = js_sys::Array::new();
-        params.push(&tx_obj);
-        Reflect::set(&request_obj, &JsValue::from_str("params"), &params)?;
+    pub async fn estimate_gas(&self, from_address: Option<String>, value: Option<String>, gas: Option<String>) -> Result<String, JsValue> {
+        Ok(self.estimate_deploy_gas(from_address, value.as_deref(), gas.as_deref()).await?.to_string())
+    }
+
+    /// Deploys the contract with the given constructor arguments and transaction options.
+    /// When `gas_limit` is omitted, the gas is estimated and raised by the gas buffer.
+    /// With `confirmations` above 1 (the default), the deployment is only returned once that
+    /// many blocks include it, after re-checking the receipt in case of a reorg.
+    #[wasm_bindgen]
+    pub async fn deploy(
+        &self,
+        from_address: String,
+        gas_limit: Option<u64>,
+        value: Option<String>,
+        confirmations: Option<u32>
+    ) -> Result<JsValue, JsValue> {
+        let deployment = self.deploy_and_wait(from_address, gas_limit, value, None).await?;
         
-        let request_fn = Reflect::get(&self.eth_provider, &JsValue::from_str("request"))?;
-        let request_fn = js_sys::Function::from(request_fn);
+        let confirmations = confirmations.unwrap_or(1);
+        if confirmations > 1 {
+            let receipt = Reflect::get(&deployment, &JsValue::from_str("receipt"))?;
+            let receipt = self.wait_for_confirmations(&receipt, confirmations).await?;
+            Reflect::set(&deployment, &JsValue::from_str("receipt"), &receipt)?;
+        }
         
-        let promise = request_fn.call1(&self.eth_provider, &request_obj)?;
-        let promise = Promise::from(promise);
-        let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        Ok(deployment)
+    }
+
+    /// Deploys the contract like `deploy`, invoking `progress_callback` on every receipt poll
+    /// with `{ attempt, elapsedMs, txHash }` so a UI can show how long confirmation is taking.
+    #[wasm_bindgen]
+    pub async fn deploy_with_progress(
+        &self,
+        from_address: String,
+        gas_limit: Option<u64>,
+        value: Option<String>,
+        progress_callback: Option<js_sys::Function>
+    ) -> Result<JsValue, JsValue> {
+        self.deploy_and_wait(from_address, gas_limit, value, progress_callback.as_ref()).await
+    }
+
+    /// Deploys `count` instances of the contract one after another, each with the stored
+    /// constructor arguments, and returns an array of `{ address, transactionHash }`.
+    /// If a deployment fails, the instances deployed so far are returned followed by an
+    /// `{ index, error }` entry, and no further deployments are attempted.
+    #[wasm_bindgen]
+    pub async fn deploy_many(
+        &self,
+        count: u32,
+        from_address: String,
+        gas_limit: Option<u64>,
+        value: Option<String>
+    ) -> Result<JsValue, JsValue> {
+        if count == 0 {
+            return Err(JsValue::from_str("deploy_many requires a count of at least 1"));
+        }
         
-        // Convert hex to decimal
-        if let Some(gas_hex) = result.as_string() {
-            // Remove 0x prefix if present
-            let gas_hex = if gas_hex.starts_with("0x") { &gas_hex[2..] } else { &gas_hex };
-            
-            // Parse hex to decimal
-            match u64::from_str_radix(gas_hex, 16) {
-                Ok(gas) => Ok(JsValue::from_f64(gas as f64)),
-                Err(_) => Err(JsValue::from_str("Failed to parse gas estimate")),
+        let results = js_sys::Array::new();
+        for index in 0..count {
+            let entry = Object::new();
+            match self.deploy_and_wait(from_address.clone(), gas_limit, value.clone(), None).await {
+                Ok(deployment) => {
+                    let receipt = Reflect::get(&deployment, &JsValue::from_str("receipt"))?;
+                    Reflect::set(&entry, &JsValue::from_str("address"), &Reflect::get(&receipt, &JsValue::from_str("contractAddress"))?)?;
+                    Reflect::set(&entry, &JsValue::from_str("transactionHash"), &Reflect::get(&deployment, &JsValue::from_str("transactionHash"))?)?;
+                    results.push(&entry);
+                },
+                Err(error) => {
+                    Reflect::set(&entry, &JsValue::from_str("index"), &JsValue::from_f64(index as f64))?;
+                    Reflect::set(&entry, &JsValue::from_str("error"), &JsValue::from_str(&provider_error_message(&error)))?;
+                    results.push(&entry);
+                    break;
+                },
             }
-        } else {
-            Err(JsValue::from_str("Invalid gas estimate result"))
         }
+        
+        Ok(results.into())
     }
 
-    /// Deploys the contract with the given constructor arguments and transaction options.
-    #[wasm_bindgen]
-    pub async fn deploy(&self, from_address: String, gas_limit: Option<u64>, value: Option<String>) -> Result<JsValue, JsValue> {
+    async fn deploy_and_wait(
+        &self,
+        from_address: String,
+        gas_limit: Option<u64>,
+        value: Option<String>,
+        progress_callback: Option<&js_sys::Function>
+    ) -> Result<JsValue, JsValue> {
         let encoded_data = self.encode_constructor_data()?;
         
         // Create transaction object
@@ -164,10 +302,15 @@ impl ContractDeployer {
         Reflect::set(&tx_obj, &JsValue::from_str("from"), &JsValue::from_str(&from_address))?;
         Reflect::set(&tx_obj, &JsValue::from_str("data"), &JsValue::from_str(&encoded_data))?;
         
-        // Add gas limit if provided
-        if let Some(gas) = gas_limit {
-            Reflect::set(&tx_obj, &JsValue::from_str("gas"), &JsValue::from_f64(gas as f64))?;
-        }
+        // Without an explicit gas limit, estimate one and add the configured buffer
+        let gas_limit = match gas_limit {
+            Some(gas) => gas,
+            None => {
+                let estimate = self.estimate_deploy_gas(Some(from_address.clone()), value.as_deref(), None).await?;
+                utils::apply_gas_buffer(estimate, self.gas_buffer_percent)?
+            },
+        };
+        Reflect::set(&tx_obj, &JsValue::from_str("gas"), &JsValue::from_f64(gas_limit as f64))?;
         
         // Add value if provided
         if let Some(val) = value {
@@ -187,10 +330,26 @@ impl ContractDeployer {
         
         let promise = request_fn.call1(&self.eth_provider, &request_obj)?;
         let promise = Promise::from(promise);
-        let tx_hash = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        let tx_hash = with_timeout(promise, self.request_timeout()).await?
+            .map_err(|e| JsValue::from(RustWasmError::from_provider_error(&e)))?;
         
         // Wait for transaction receipt
-        let receipt = self.wait_for_receipt(tx_hash.clone()).await?;
+        let receipt = self.wait_for_receipt(tx_hash.clone(), progress_callback).await?;
+        
+        // A reverted deployment has no contract address, so report the failure itself
+        let status = Reflect::get(&receipt, &JsValue::from_str("status"))?;
+        if status.as_string().as_deref() == Some("0x0") {
+            let gas_used = Reflect::get(&receipt, &JsValue::from_str("gasUsed"))?
+                .as_string()
+                .and_then(|gas| u64::from_str_radix(gas.trim_start_matches("0x"), 16).ok());
+            return Err(JsValue::from_str(&match gas_used {
+                Some(gas_used) => format!(
+                    "Contract deployment failed (transaction reverted) after using {} of the {} gas limit",
+                    gas_used, gas_limit
+                ),
+                None => "Contract deployment failed (transaction reverted)".to_string(),
+            }));
+        }
         
         // Create result object
         let result = Object::new();
@@ -199,9 +358,10 @@ impl ContractDeployer {
         
         // Create contract instance
         let contract_address = Reflect::get(&receipt, &JsValue::from_str("contractAddress"))?;
-        let contract = Contract::new(
+        let contract = Contract::with_provider(
             contract_address.as_string().ok_or_else(|| JsValue::from_str("Invalid contract address"))?,
-            self.abi.clone()
+            self.abi.clone(),
+            self.eth_provider.clone()
         )?;
         
         // Convert contract to JsValue before setting it
@@ -211,10 +371,10 @@ impl ContractDeployer {
         Ok(JsValue::from(result))
     }
 
-    /// Wait for transaction receipt
-    async fn wait_for_receipt(&self, tx_hash: JsValue) -> Result<JsValue, JsValue> {
+    /// Wait for transaction receipt, reporting each poll to the optional progress callback
+    async fn wait_for_receipt(&self, tx_hash: JsValue, progress_callback: Option<&js_sys::Function>) -> Result<JsValue, JsValue> {
         // Function to get transaction receipt
-        async fn get_receipt(provider: &JsValue, tx_hash: &JsValue) -> Result<JsValue, JsValue> {
+        async fn get_receipt(provider: &JsValue, tx_hash: &JsValue, timeout_ms: u32) -> Result<JsValue, JsValue> {
             let request_obj = Object::new();
             Reflect::set(&request_obj, &JsValue::from_str("method"), &JsValue::from_str("eth_getTransactionReceipt"))?;
             
@@ -227,16 +387,28 @@ impl ContractDeployer {
             
             let promise = request_fn.call1(provider, &request_obj)?;
             let promise = Promise::from(promise);
-            wasm_bindgen_futures::JsFuture::from(promise).await
+            with_timeout(promise, timeout_ms).await?
+                .map_err(|e| JsValue::from(RustWasmError::from_provider_error(&e)))
         }
         
         // Poll for receipt with exponential backoff
         let mut attempts = 0;
-        let max_attempts = 50;
-        let mut delay_ms = 1000;
-        
-        while attempts < max_attempts {
-            let receipt = get_receipt(&self.eth_provider, &tx_hash).await?;
+        let mut delay_ms = self.poll_config.initial_delay_ms();
+        let started_at = js_sys::Date::now();
+        
+        while attempts < self.poll_config.max_attempts() {
+            if let Some(callback) = progress_callback {
+                let progress = Object::new();
+                Reflect::set(&progress, &JsValue::from_str("attempt"), &JsValue::from_f64((attempts + 1) as f64))?;
+                Reflect::set(&progress, &JsValue::from_str("elapsedMs"), &JsValue::from_f64(js_sys::Date::now() - started_at))?;
+                Reflect::set(&progress, &JsValue::from_str("txHash"), &tx_hash)?;
+                // A failing callback must not abort waiting for an already-sent deployment
+                if callback.call1(&JsValue::null(), &progress).is_err() {
+                    utils::log_warning("Deployment progress callback threw an error");
+                }
+            }
+            
+            let receipt = get_receipt(&self.eth_provider, &tx_hash, self.request_timeout()).await?;
             
             if !receipt.is_null() && !receipt.is_undefined() {
                 return Ok(receipt);
@@ -249,7 +421,7 @@ impl ContractDeployer {
                     let window = web_sys::window().unwrap();
                     let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
                         &resolve, 
-                        delay_ms
+                        delay_ms as i32
                     );
                 });
                 
@@ -259,16 +431,63 @@ impl ContractDeployer {
             #[cfg(not(target_arch = "wasm32"))]
             {
                 // Simulated delay in non-wasm environment
-                sleep(Duration::from_millis(delay_ms));
+                sleep(Duration::from_millis(delay_ms as u64));
             }
             
             attempts += 1;
-            delay_ms = std::cmp::min(delay_ms * 2, 10000);
+            delay_ms = self.poll_config.next_delay(delay_ms);
         }
         
         Err(JsValue::from_str("Transaction receipt not found after maximum attempts"))
     }
 
+    /// Waits until the chain is `confirmations` blocks past the receipt's block, then
+    /// re-fetches the receipt to make sure the deployment survived any reorg
+    async fn wait_for_confirmations(&self, receipt: &JsValue, confirmations: u32) -> Result<JsValue, JsValue> {
+        let tx_hash = Reflect::get(receipt, &JsValue::from_str("transactionHash"))?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Receipt has no transaction hash"))?;
+        let block_number = Reflect::get(receipt, &JsValue::from_str("blockNumber"))?
+            .as_string()
+            .and_then(|block| u64::from_str_radix(block.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| JsValue::from_str("Receipt has no block number"))?;
+        let target_block = block_number + confirmations as u64 - 1;
+        
+        let mut attempts = 0;
+        let mut delay_ms = self.poll_config.initial_delay_ms();
+        while self.block_number().await? < target_block {
+            attempts += 1;
+            if attempts >= self.poll_config.max_attempts() {
+                return Err(JsValue::from_str(&format!(
+                    "Deployment {} did not reach {} confirmations after maximum attempts", tx_hash, confirmations
+                )));
+            }
+            crate::eth_integration::sleep_ms(delay_ms).await?;
+            delay_ms = self.poll_config.next_delay(delay_ms);
+        }
+        
+        let receipt = crate::eth_integration::request_with_provider(
+            &self.eth_provider, "eth_getTransactionReceipt", Array::of1(&JsValue::from_str(&tx_hash)), &|_| None,
+        ).await?;
+        if receipt.is_null() || receipt.is_undefined() {
+            return Err(JsValue::from_str(&format!("Deployment {} was removed from the chain by a reorg", tx_hash)));
+        }
+        if Reflect::get(&receipt, &JsValue::from_str("status"))?.as_string().as_deref() != Some("0x1") {
+            return Err(JsValue::from_str(&format!("Deployment {} reverted after a reorg", tx_hash)));
+        }
+        
+        Ok(receipt)
+    }
+
+    /// Get the latest block number from the deployer's provider
+    async fn block_number(&self) -> Result<u64, JsValue> {
+        let block_number = crate::eth_integration::request_with_provider(&self.eth_provider, "eth_blockNumber", Array::new(), &|_| None).await?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Invalid eth_blockNumber result"))?;
+        u64::from_str_radix(block_number.trim_start_matches("0x"), 16)
+            .map_err(|_| JsValue::from_str(&format!("Failed to parse block number: {}", block_number)))
+    }
+
     /// Create a collection from an existing contract
     #[wasm_bindgen]
     pub fn create_collection(&self, contracts: js_sys::Array) -> Result<JsValue, JsValue> {
@@ -289,7 +508,7 @@ impl ContractDeployer {
             let addr_str = address.as_string().ok_or_else(|| JsValue::from_str("Invalid contract address"))?;
             
             // Create Contract instance
-            let contract = Contract::new(addr_str, self.abi.clone())?;
+            let contract = Contract::with_provider(addr_str, self.abi.clone(), self.eth_provider.clone())?;
             
             // Add to result object instead of HashMap (which requires Serialize)
             Reflect::set(&result, &name, &JsValue::from(contract))?;
@@ -364,6 +583,7 @@ impl ContractDeployer {
         let contract = deployer.deploy(
             options.as_string().ok_or_else(|| JsValue::from_str("From address required"))?,
             None,
+            None,
             None
         ).await?;
         
@@ -387,4 +607,87 @@ impl ContractDeployer {
         
         Ok(JsValue::from(result))
     }
-} 
\ No newline at end of file
+} 
+
+impl ContractDeployer {
+    /// Estimates the deployment gas with eth_estimateGas, see `estimate_gas`
+    async fn estimate_deploy_gas(&self, from_address: Option<String>, value: Option<&str>, gas: Option<&str>) -> Result<u64, JsValue> {
+        let encoded_data = self.encode_constructor_data()?;
+        
+        let from_address = match from_address.filter(|address| !address.is_empty()) {
+            Some(address) => address,
+            None => crate::eth_integration::require_account_with_provider(&self.eth_provider).await?,
+        };
+        
+        // Create transaction object
+        let tx_obj = Object::new();
+        Reflect::set(&tx_obj, &JsValue::from_str("from"), &JsValue::from_str(&from_address))?;
+        Reflect::set(&tx_obj, &JsValue::from_str("data"), &JsValue::from_str(&encoded_data))?;
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            let value = utils::parse_quantity(value, "value")?;
+            Reflect::set(&tx_obj, &JsValue::from_str("value"), &JsValue::from_str(&format!("0x{:x}", value)))?;
+        }
+        let gas_ceiling = match gas.filter(|gas| !gas.is_empty()) {
+            Some(gas) => {
+                let gas = utils::parse_quantity(gas, "gas")?;
+                Reflect::set(&tx_obj, &JsValue::from_str("gas"), &JsValue::from_str(&format!("0x{:x}", gas)))?;
+                Some(gas)
+            },
+            None => None,
+        };
+        
+        // Call estimateGas method on provider
+        let request_obj = Object::new();
+        Reflect::set(&request_obj, &JsValue::from_str("method"), &JsValue::from_str("eth_estimateGas"))?;
+        
+        let params = js_sys::Array::new();
+        params.push(&tx_obj);
+        Reflect::set(&request_obj, &JsValue::from_str("params"), &params)?;
+        
+        let request_fn = Reflect::get(&self.eth_provider, &JsValue::from_str("request"))?;
+        let request_fn = js_sys::Function::from(request_fn);
+        
+        let promise = request_fn.call1(&self.eth_provider, &request_obj)?;
+        let promise = Promise::from(promise);
+        let result = with_timeout(promise, self.request_timeout()).await?.map_err(|e| {
+            // Include the data size so oversized or malformed constructor args are easy to spot
+            let message = provider_error_message(&e);
+            let data_length = encoded_data.trim_start_matches("0x").len() / 2;
+            JsValue::from_str(&format!(
+                "Gas estimation failed for {} bytes of deployment data (bytecode + constructor args): {}",
+                data_length, message
+            ))
+        })?;
+        
+        // Convert hex to decimal
+        let estimate = match result.as_string() {
+            Some(gas_hex) => crate::contract::normalize_hex_digits(&gas_hex).ok()
+                .filter(|digits| !digits.is_empty())
+                .and_then(|digits| u64::from_str_radix(&digits, 16).ok())
+                .ok_or_else(|| JsValue::from_str(&format!("Failed to parse gas estimate: {}", gas_hex)))?,
+            None => return Err(JsValue::from_str("Invalid gas estimate result")),
+        };
+        
+        // Some nodes return the cap itself instead of failing when it is too low
+        if let Some(gas_ceiling) = gas_ceiling {
+            if primitive_types::U256::from(estimate) > gas_ceiling {
+                return Err(JsValue::from_str(&format!(
+                    "Deployment needs {} gas, more than the {} gas limit", estimate, gas_ceiling
+                )));
+            }
+        }
+        
+        Ok(estimate)
+    }
+}
+
+/// Unwraps a compiler artifact passed as the ABI, taking its bytecode when none was given
+fn split_artifact(bytecode: String, abi: String) -> Result<(String, String), JsValue> {
+    let abi_array = normalize_abi_json(&abi).map_err(|e| JsValue::from(RustWasmError::AbiParse(e.to_string())))?;
+    let bytecode = if bytecode.is_empty() {
+        artifact_bytecode(&abi).unwrap_or_default()
+    } else {
+        bytecode
+    };
+    Ok((bytecode, abi_array))
+}
@@ -0,0 +1,293 @@
+use wasm_bindgen::prelude::*;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::utils::is_valid_eth_address;
+use crate::error::RustWasmError;
+use super::codec::{encode, integer_token, parse_integer, Token};
+use super::events::ParamType;
+use super::utils::{bytes_to_hex, hex_to_bytes, keccak256};
+
+/// Type string of the standard EIP-712 domain with all four common fields
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// Computes the EIP-712 domain separator
+/// `keccak256(abi.encode(typeHash, keccak256(name), keccak256(version), chainId, verifyingContract))`
+/// for the standard `EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)`
+/// domain, returning the 32-byte hash as `0x`-prefixed hex. Tokens exposing `DOMAIN_SEPARATOR()`
+/// return the same value, which makes it easy to check the inputs against a deployed contract.
+#[wasm_bindgen]
+pub fn domain_separator(name: &str, version: &str, chain_id: u64, verifying_contract: &str) -> Result<String, JsValue> {
+    if !is_valid_eth_address(verifying_contract) {
+        return Err(RustWasmError::InvalidAddress(verifying_contract.to_string()).into());
+    }
+    let contract_bytes = hex_to_bytes(verifying_contract).map_err(|e| JsValue::from_str(&e))?;
+    
+    let mut chain_id_word = [0u8; 32];
+    chain_id_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+    let mut contract_word = [0u8; 32];
+    contract_word[12..].copy_from_slice(&contract_bytes);
+    
+    let mut encoded = Vec::with_capacity(5 * 32);
+    encoded.extend_from_slice(&keccak256(EIP712_DOMAIN_TYPE.as_bytes()));
+    encoded.extend_from_slice(&keccak256(name.as_bytes()));
+    encoded.extend_from_slice(&keccak256(version.as_bytes()));
+    encoded.extend_from_slice(&chain_id_word);
+    encoded.extend_from_slice(&contract_word);
+    
+    Ok(bytes_to_hex(&keccak256(&encoded)))
+}
+
+/// A member of a struct type in the typed-data `types` map
+#[derive(Deserialize)]
+struct TypedField {
+    name: String,
+    #[serde(rename = "type")]
+    r#type: String,
+}
+
+/// The standard typed-data JSON accepted by `eth_signTypedData_v4`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TypedData {
+    types: BTreeMap<String, Vec<TypedField>>,
+    primary_type: String,
+    domain: Value,
+    #[serde(default)]
+    message: Value,
+}
+
+/// Computes the EIP-712 digest `keccak256(0x1901 ++ domainSeparator ++ hashStruct(message))`
+/// from standard typed-data JSON (`{ types, primaryType, domain, message }`), i.e. exactly
+/// the hash a wallet signs for `eth_signTypedData_v4`. `types` must include `EIP712Domain`.
+/// Nested struct types and arrays are hashed recursively; integers may be JSON numbers or
+/// decimal/hex strings. Unknown types and cyclic type references are rejected.
+#[wasm_bindgen]
+pub fn hash_typed_data(typed_data_json: &str) -> Result<String, JsValue> {
+    let typed_data: TypedData = serde_json::from_str(typed_data_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid typed data JSON: {}", e)))?;
+    
+    let digest = typed_data_digest(&typed_data).map_err(|e| JsValue::from_str(&e))?;
+    Ok(bytes_to_hex(&digest))
+}
+
+pub(super) fn typed_data_digest(typed_data: &TypedData) -> Result<[u8; 32], String> {
+    let types = &typed_data.types;
+    if !types.contains_key("EIP712Domain") {
+        return Err("Typed data is missing the EIP712Domain type".to_string());
+    }
+    if !types.contains_key(&typed_data.primary_type) {
+        return Err(format!("Primary type '{}' is not defined in types", typed_data.primary_type));
+    }
+    
+    let mut encoded = vec![0x19, 0x01];
+    encoded.extend_from_slice(&hash_struct(types, "EIP712Domain", &typed_data.domain)?);
+    // A domain-only payload signs just the separator, as the spec prescribes
+    if typed_data.primary_type != "EIP712Domain" {
+        encoded.extend_from_slice(&hash_struct(types, &typed_data.primary_type, &typed_data.message)?);
+    }
+    
+    Ok(keccak256(&encoded))
+}
+
+/// Strips any array suffixes, e.g. `Person[][2]` becomes `Person`
+fn base_type(type_name: &str) -> &str {
+    type_name.split('[').next().unwrap_or(type_name)
+}
+
+/// Collects the struct types `type_name` references, directly or transitively, checking
+/// that every member type is either defined or atomic and that no reference forms a cycle
+fn collect_dependencies(
+    types: &BTreeMap<String, Vec<TypedField>>,
+    type_name: &str,
+    path: &mut Vec<String>,
+    found: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    if let Some(start) = path.iter().position(|name| name == type_name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(type_name.to_string());
+        return Err(format!("Cyclic type reference: {}", cycle.join(" -> ")));
+    }
+    if found.contains(type_name) {
+        return Ok(());
+    }
+    
+    let fields = types.get(type_name)
+        .ok_or_else(|| format!("Type '{}' is not defined in types", type_name))?;
+    path.push(type_name.to_string());
+    for field in fields {
+        let member_type = base_type(&field.r#type);
+        if types.contains_key(member_type) {
+            collect_dependencies(types, member_type, path, found)?;
+        } else if ParamType::from_str(member_type).is_none() {
+            return Err(format!("Unknown type '{}' for field '{}' of {}", field.r#type, field.name, type_name));
+        }
+    }
+    path.pop();
+    found.insert(type_name.to_string());
+    
+    Ok(())
+}
+
+/// Builds `encodeType`: the primary type followed by its dependencies sorted by name,
+/// e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`
+fn encode_type(types: &BTreeMap<String, Vec<TypedField>>, type_name: &str) -> Result<String, String> {
+    let mut found = BTreeSet::new();
+    collect_dependencies(types, type_name, &mut Vec::new(), &mut found)?;
+    found.remove(type_name);
+    
+    let mut encoded = String::new();
+    for name in std::iter::once(type_name).chain(found.iter().map(String::as_str)) {
+        let members: Vec<String> = types[name].iter()
+            .map(|field| format!("{} {}", field.r#type, field.name))
+            .collect();
+        encoded.push_str(&format!("{}({})", name, members.join(",")));
+    }
+    
+    Ok(encoded)
+}
+
+/// Computes `hashStruct(value) = keccak256(typeHash ++ encodeData(value))`
+fn hash_struct(types: &BTreeMap<String, Vec<TypedField>>, type_name: &str, value: &Value) -> Result<[u8; 32], String> {
+    let object = value.as_object()
+        .ok_or_else(|| format!("Expected an object for {}", type_name))?;
+    
+    let mut encoded = keccak256(encode_type(types, type_name)?.as_bytes()).to_vec();
+    for field in &types[type_name] {
+        let member = object.get(&field.name)
+            .ok_or_else(|| format!("Missing field '{}' of {}", field.name, type_name))?;
+        let word = encode_member(types, &field.r#type, member)
+            .map_err(|e| format!("Invalid field '{}' of {}: {}", field.name, type_name, e))?;
+        encoded.extend_from_slice(&word);
+    }
+    
+    Ok(keccak256(&encoded))
+}
+
+/// Encodes one member as a 32-byte word: structs by their `hashStruct`, `string`/`bytes`
+/// and arrays by the hash of their contents, and atomic types as in ABI encoding
+fn encode_member(types: &BTreeMap<String, Vec<TypedField>>, type_name: &str, value: &Value) -> Result<[u8; 32], String> {
+    if let Some((element_type, length)) = type_name.strip_suffix(']').and_then(|rest| rest.rsplit_once('[')) {
+        let elements = value.as_array()
+            .ok_or_else(|| format!("Expected an array for {}", type_name))?;
+        if !length.is_empty() && length.parse::<usize>().ok() != Some(elements.len()) {
+            return Err(format!("Expected {} elements for {}, got {}", length, type_name, elements.len()));
+        }
+        
+        let mut encoded = Vec::with_capacity(elements.len() * 32);
+        for element in elements {
+            encoded.extend_from_slice(&encode_member(types, element_type, element)?);
+        }
+        return Ok(keccak256(&encoded));
+    }
+    
+    if types.contains_key(type_name) {
+        return hash_struct(types, type_name, value);
+    }
+    
+    let param_type = ParamType::from_str(type_name)
+        .ok_or_else(|| format!("Unknown type '{}'", type_name))?;
+    let token = match param_type {
+        ParamType::String => return Ok(keccak256(expect_str(type_name, value)?.as_bytes())),
+        ParamType::Bytes => return Ok(keccak256(&hex_to_bytes(expect_str(type_name, value)?)?)),
+        ParamType::Address => {
+            let address = expect_str(type_name, value)?;
+            if !is_valid_eth_address(address) {
+                return Err(format!("Invalid Ethereum address: {}", address));
+            }
+            Token::Address(address.to_string())
+        },
+        ParamType::Bool => Token::Bool(value.as_bool()
+            .ok_or_else(|| format!("Expected a boolean for bool, got {}", value))?),
+        ParamType::Uint(bits) | ParamType::Int(bits) => {
+            let text = match value {
+                Value::Number(number) => number.to_string(),
+                Value::String(text) => text.clone(),
+                _ => return Err(format!("Expected a number or string for {}, got {}", type_name, value)),
+            };
+            let (negative, magnitude) = parse_integer(&text)?;
+            integer_token(negative, magnitude, matches!(param_type, ParamType::Int(_)), bits)?
+        },
+        ParamType::FixedBytes(size) => {
+            let bytes = hex_to_bytes(expect_str(type_name, value)?)?;
+            if bytes.len() != size {
+                return Err(format!("Expected {} bytes for {}, got {}", size, type_name, bytes.len()));
+            }
+            Token::FixedBytes(bytes)
+        },
+        _ => return Err(format!("Unsupported type '{}'", type_name)),
+    };
+    
+    let mut word = [0u8; 32];
+    word.copy_from_slice(&encode(&[token])?);
+    Ok(word)
+}
+
+fn expect_str<'a>(type_name: &str, value: &'a Value) -> Result<&'a str, String> {
+    value.as_str().ok_or_else(|| format!("Expected a string for {}, got {}", type_name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_separator_matches_deployed_tokens() {
+        // USDC on mainnet returns this from DOMAIN_SEPARATOR()
+        assert_eq!(
+            domain_separator("USD Coin", "2", 1, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+            "0x06c37168a7db5138defc7866392bb87a741f9b3d104deb5094588ce041cae335"
+        );
+        // The domain of the Mail example in the EIP-712 specification
+        assert_eq!(
+            domain_separator("Ether Mail", "1", 1, "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap(),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+    }
+
+    #[test]
+    fn hash_typed_data_matches_specification_example() {
+        let mail = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        });
+
+        let typed_data: TypedData = serde_json::from_value(mail.clone()).unwrap();
+        assert_eq!(
+            bytes_to_hex(&typed_data_digest(&typed_data).unwrap()),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        assert_eq!(
+            hash_typed_data(&mail.to_string()).unwrap(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+}
@@ -1,11 +1,24 @@
 use wasm_bindgen::prelude::*;
-use super::Contract;
+use std::cell::RefCell;
+use primitive_types::U256;
+use super::{event_log_from_js, Contract, LogValue};
+use super::codec::{decode, encode_function_data, Token};
+use super::events::ParamType;
+use super::multicall::aggregate3;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use crate::eth_integration::rpc_batch;
+use crate::utils::{addresses_equal, normalize_address};
 
 /// ERC-20 token standard implementation.
 /// This is a specialized interface for interacting with ERC-20 token contracts.
 #[wasm_bindgen]
 pub struct ERC20Token {
     contract: Contract,
+    multicall_address: Option<String>,
+    // Token metadata is immutable, so the first successful fetch is reused
+    name_cache: RefCell<Option<String>>,
+    symbol_cache: RefCell<Option<String>>,
+    decimals_cache: RefCell<Option<u8>>,
 }
 
 const ERC20_ABI: &str = r#"[
@@ -131,7 +144,26 @@ impl ERC20Token {
     #[wasm_bindgen(constructor)]
     pub fn new(token_address: String) -> Result<ERC20Token, JsValue> {
         let contract = Contract::new(token_address, ERC20_ABI.to_string())?;
-        Ok(ERC20Token { contract })
+        Ok(ERC20Token {
+            contract,
+            multicall_address: None,
+            name_cache: RefCell::new(None),
+            symbol_cache: RefCell::new(None),
+            decimals_cache: RefCell::new(None),
+        })
+    }
+
+    /// Creates an ERC20Token for another token address, reusing this token's parsed ABI
+    /// instead of parsing it again. The multicall address is kept; cached metadata is not.
+    #[wasm_bindgen]
+    pub fn for_address(&self, token_address: String) -> Result<ERC20Token, JsValue> {
+        Ok(ERC20Token {
+            contract: self.contract.at_address(&token_address)?,
+            multicall_address: self.multicall_address.clone(),
+            name_cache: RefCell::new(None),
+            symbol_cache: RefCell::new(None),
+            decimals_cache: RefCell::new(None),
+        })
     }
 
     /// Gets the token contract address.
@@ -140,37 +172,67 @@ impl ERC20Token {
         self.contract.address()
     }
 
-    /// Gets the token name.
+    /// Gets the multicall contract address used for batched reads, if any.
+    #[wasm_bindgen(getter)]
+    pub fn multicall_address(&self) -> Option<String> {
+        self.multicall_address.clone()
+    }
+
+    /// Sets the multicall (Multicall3) contract address used for batched reads.
+    #[wasm_bindgen(setter)]
+    pub fn set_multicall_address(&mut self, multicall_address: Option<String>) {
+        self.multicall_address = multicall_address;
+    }
+
+    /// Gets the token name. The result is cached after the first successful call.
     #[wasm_bindgen]
     pub async fn name(&self) -> Result<String, JsValue> {
+        if let Some(name) = self.name_cache.borrow().clone() {
+            return Ok(name);
+        }
+        
         let result = self.contract.call("name", JsValue::from(js_sys::Array::new())).await?;
         
         match result.as_string() {
-            Some(name) => Ok(name),
+            Some(name) => {
+                *self.name_cache.borrow_mut() = Some(name.clone());
+                Ok(name)
+            },
             None => Err(JsValue::from_str("Failed to parse token name"))
         }
     }
 
-    /// Gets the token symbol.
+    /// Gets the token symbol. The result is cached after the first successful call.
     #[wasm_bindgen]
     pub async fn symbol(&self) -> Result<String, JsValue> {
+        if let Some(symbol) = self.symbol_cache.borrow().clone() {
+            return Ok(symbol);
+        }
+        
         let result = self.contract.call("symbol", JsValue::from(js_sys::Array::new())).await?;
         
         match result.as_string() {
-            Some(symbol) => Ok(symbol),
+            Some(symbol) => {
+                *self.symbol_cache.borrow_mut() = Some(symbol.clone());
+                Ok(symbol)
+            },
             None => Err(JsValue::from_str("Failed to parse token symbol"))
         }
     }
 
-    /// Gets the token decimals.
+    /// Gets the token decimals. The result is cached after the first successful call.
     #[wasm_bindgen]
     pub async fn decimals(&self) -> Result<u8, JsValue> {
+        if let Some(decimals) = *self.decimals_cache.borrow() {
+            return Ok(decimals);
+        }
+        
         let result = self.contract.call("decimals", JsValue::from(js_sys::Array::new())).await?;
         
         match result.as_string() {
             Some(decimals_str) => {
-                // Convert hexadecimal string to decimal
-                if let Ok(decimals) = u8::from_str_radix(decimals_str.trim_start_matches("0x"), 16) {
+                if let Ok(decimals) = decimals_str.parse::<u8>() {
+                    *self.decimals_cache.borrow_mut() = Some(decimals);
                     Ok(decimals)
                 } else {
                     Err(JsValue::from_str("Failed to parse token decimals"))
@@ -180,6 +242,90 @@ impl ERC20Token {
         }
     }
 
+    /// Fetches `name`, `symbol`, `decimals` and `totalSupply` in one round-trip, through multicall
+    /// when configured and a batched RPC request otherwise.
+    /// Returns `{ name, symbol, decimals, totalSupply }` with the supply as a decimal string.
+    /// Cached metadata is reused, so after the first call only the supply is fetched.
+    #[wasm_bindgen]
+    pub async fn metadata(&self) -> Result<JsValue, JsValue> {
+        let cached_name = self.name_cache.borrow().clone();
+        let cached_symbol = self.symbol_cache.borrow().clone();
+        let cached_decimals = *self.decimals_cache.borrow();
+
+        let mut signatures = Vec::new();
+        if cached_name.is_none() {
+            signatures.push("name()");
+        }
+        if cached_symbol.is_none() {
+            signatures.push("symbol()");
+        }
+        if cached_decimals.is_none() {
+            signatures.push("decimals()");
+        }
+        signatures.push("totalSupply()");
+
+        let mut calls = Vec::new();
+        for signature in &signatures {
+            let data = encode_function_data(signature, &[]).map_err(|e| JsValue::from_str(&e))?;
+            calls.push((self.contract.address(), data));
+        }
+
+        let results = self.fetch_calls(&calls).await?;
+        let mut name = cached_name;
+        let mut symbol = cached_symbol;
+        let mut decimals = cached_decimals;
+        let mut total_supply = None;
+
+        for (signature, data) in signatures.iter().zip(results) {
+            let data = data.ok_or_else(|| JsValue::from_str(&format!("Token call {} failed", signature)))?;
+            let output_type = match *signature {
+                "name()" | "symbol()" => ParamType::String,
+                "decimals()" => ParamType::Uint(8),
+                _ => ParamType::Uint(256),
+            };
+            let token = decode(&[output_type], &data).ok()
+                .and_then(|tokens| tokens.into_iter().next())
+                .ok_or_else(|| JsValue::from_str(&format!("Failed to decode the result of {}", signature)))?;
+
+            match (*signature, token) {
+                ("name()", Token::String(value)) => name = Some(value),
+                ("symbol()", Token::String(value)) => symbol = Some(value),
+                ("decimals()", Token::Uint(value)) => {
+                    if value > U256::from(u8::MAX) {
+                        return Err(JsValue::from_str("Failed to parse token decimals"));
+                    }
+                    decimals = Some(value.low_u32() as u8);
+                },
+                (_, token) => total_supply = Some(token.to_decimal_string()),
+            }
+        }
+
+        let (name, symbol, decimals, total_supply) = match (name, symbol, decimals, total_supply) {
+            (Some(name), Some(symbol), Some(decimals), Some(total_supply)) => (name, symbol, decimals, total_supply),
+            _ => return Err(JsValue::from_str("Failed to fetch token metadata")),
+        };
+
+        *self.name_cache.borrow_mut() = Some(name.clone());
+        *self.symbol_cache.borrow_mut() = Some(symbol.clone());
+        *self.decimals_cache.borrow_mut() = Some(decimals);
+
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("name"), &JsValue::from_str(&name))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("symbol"), &JsValue::from_str(&symbol))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("decimals"), &JsValue::from_f64(decimals as f64))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("totalSupply"), &JsValue::from_str(&total_supply))?;
+
+        Ok(result.into())
+    }
+
+    /// Clears the cached name, symbol and decimals so they are fetched again on next use.
+    #[wasm_bindgen]
+    pub fn refresh_metadata(&self) {
+        *self.name_cache.borrow_mut() = None;
+        *self.symbol_cache.borrow_mut() = None;
+        *self.decimals_cache.borrow_mut() = None;
+    }
+
     /// Gets the total supply of the token.
     #[wasm_bindgen]
     pub async fn total_supply(&self) -> Result<String, JsValue> {
@@ -191,11 +337,43 @@ impl ERC20Token {
         }
     }
 
+    /// Gets the total supply formatted with the token's decimals, e.g. "1000000.5".
+    #[wasm_bindgen]
+    pub async fn total_supply_formatted(&self) -> Result<String, JsValue> {
+        let supply = self.total_supply().await?;
+        self.format_units(&supply, None).await
+    }
+
+    /// Estimates the market cap as total supply times `price_per_token`, a non-negative
+    /// decimal string such as "0.25". Uses exact fixed-point arithmetic and returns a
+    /// decimal string without trailing zeros.
+    #[wasm_bindgen]
+    pub async fn market_cap_estimate(&self, price_per_token: &str) -> Result<String, JsValue> {
+        let price_scale = price_per_token.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+        let price_scale = u8::try_from(price_scale)
+            .map_err(|_| JsValue::from_str(&format!("Invalid price '{}': too many decimal places", price_per_token)))?;
+        let raw_price = parse_amount(price_per_token, price_scale).map_err(|e| JsValue::from_str(&e))?;
+        
+        let supply = self.total_supply().await?;
+        let decimals = self.decimals().await?;
+        
+        let parse = |value: &str| U256::from_dec_str(value)
+            .map_err(|_| JsValue::from_str(&format!("Invalid amount: {}", value)));
+        let market_cap = parse(&supply)?.checked_mul(parse(&raw_price)?)
+            .ok_or_else(|| JsValue::from_str("Market cap estimate overflows 256 bits"))?;
+        let scale = decimals.checked_add(price_scale)
+            .ok_or_else(|| JsValue::from_str(&format!("Invalid price '{}': too many decimal places", price_per_token)))?;
+        
+        crate::utils::format_units(&market_cap.to_string(), scale)
+    }
+
     /// Gets the balance of the given address.
     #[wasm_bindgen]
     pub async fn balance_of(&self, owner: &str) -> Result<String, JsValue> {
+        let owner = normalize_address(owner)?;
+        
         let args = js_sys::Array::new();
-        args.push(&JsValue::from_str(owner));
+        args.push(&JsValue::from_str(&owner));
         
         let result = self.contract.call("balanceOf", args.into()).await?;
         
@@ -205,12 +383,66 @@ impl ERC20Token {
         }
     }
 
+    /// Gets the balance of the given address formatted with the token's decimals.
+    #[wasm_bindgen]
+    pub async fn balance_of_formatted(&self, owner: &str) -> Result<String, JsValue> {
+        let balance = self.balance_of(owner).await?;
+        self.format_units(&balance, None).await
+    }
+
+    /// Gets the balances of several addresses, batched through multicall when configured.
+    /// Returns an array of decimal balance strings in input order, with `null` for entries that failed.
+    #[wasm_bindgen]
+    pub async fn balance_of_many(&self, owners: js_sys::Array) -> Result<JsValue, JsValue> {
+        let mut owner_addresses = Vec::new();
+        for owner in owners.iter() {
+            owner_addresses.push(normalize_address(&owner.as_string().unwrap_or_default())?);
+        }
+        
+        let balances = js_sys::Array::new();
+        
+        match &self.multicall_address {
+            Some(multicall_address) => {
+                let mut calls = Vec::new();
+                for owner in &owner_addresses {
+                    let data = encode_function_data("balanceOf(address)", &[Token::Address(owner.clone())])
+                        .map_err(|e| JsValue::from_str(&e))?;
+                    calls.push((self.contract.address(), data));
+                }
+                
+                for result in aggregate3(multicall_address, &calls).await? {
+                    let balance = result
+                        .and_then(|data| decode(&[ParamType::Uint(256)], &data).ok())
+                        .and_then(|tokens| tokens.first().map(|t| t.to_decimal_string()));
+                    
+                    match balance {
+                        Some(balance) => balances.push(&JsValue::from_str(&balance)),
+                        None => balances.push(&JsValue::null()),
+                    };
+                }
+            },
+            None => {
+                for owner in &owner_addresses {
+                    match self.balance_of(owner).await {
+                        Ok(balance) => balances.push(&JsValue::from_str(&balance)),
+                        Err(_) => balances.push(&JsValue::null()),
+                    };
+                }
+            },
+        }
+        
+        Ok(balances.into())
+    }
+
     /// Gets the allowance for a spender from an owner.
     #[wasm_bindgen]
     pub async fn allowance(&self, owner: &str, spender: &str) -> Result<String, JsValue> {
+        let owner = normalize_address(owner)?;
+        let spender = normalize_address(spender)?;
+        
         let args = js_sys::Array::new();
-        args.push(&JsValue::from_str(owner));
-        args.push(&JsValue::from_str(spender));
+        args.push(&JsValue::from_str(&owner));
+        args.push(&JsValue::from_str(&spender));
         
         let result = self.contract.call("allowance", args.into()).await?;
         
@@ -223,18 +455,31 @@ impl ERC20Token {
     /// Transfers tokens to the given address.
     #[wasm_bindgen]
     pub async fn transfer(&self, to: &str, amount: &str, options: JsValue) -> Result<String, JsValue> {
+        let to = normalize_address(to)?;
+        
         let args = js_sys::Array::new();
-        args.push(&JsValue::from_str(to));
+        args.push(&JsValue::from_str(&to));
         args.push(&JsValue::from_str(amount));
         
         self.contract.send_transaction("transfer", args.into(), options).await
     }
 
+    /// Transfers a human-readable amount of tokens (e.g. "1.5"), converting it
+    /// to the raw amount using the token's decimals.
+    #[wasm_bindgen]
+    pub async fn transfer_tokens(&self, to: &str, human_amount: &str, options: JsValue) -> Result<String, JsValue> {
+        normalize_address(to)?;
+        let raw_amount = self.parse_units(human_amount, None).await?;
+        self.transfer(to, &raw_amount, options).await
+    }
+
     /// Approves a spender to use tokens on behalf of the sender.
     #[wasm_bindgen]
     pub async fn approve(&self, spender: &str, amount: &str, options: JsValue) -> Result<String, JsValue> {
+        let spender = normalize_address(spender)?;
+        
         let args = js_sys::Array::new();
-        args.push(&JsValue::from_str(spender));
+        args.push(&JsValue::from_str(&spender));
         args.push(&JsValue::from_str(amount));
         
         self.contract.send_transaction("approve", args.into(), options).await
@@ -243,9 +488,12 @@ impl ERC20Token {
     /// Transfers tokens from one address to another, requires approval.
     #[wasm_bindgen]
     pub async fn transfer_from(&self, from: &str, to: &str, amount: &str, options: JsValue) -> Result<String, JsValue> {
+        let from = normalize_address(from)?;
+        let to = normalize_address(to)?;
+        
         let args = js_sys::Array::new();
-        args.push(&JsValue::from_str(from));
-        args.push(&JsValue::from_str(to));
+        args.push(&JsValue::from_str(&from));
+        args.push(&JsValue::from_str(&to));
         args.push(&JsValue::from_str(amount));
         
         self.contract.send_transaction("transferFrom", args.into(), options).await
@@ -257,14 +505,14 @@ impl ERC20Token {
         let indexed_params = js_sys::Object::new();
         
         if let Some(from_addr) = from {
-            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("from"), &JsValue::from_str(&from_addr))?;
+            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("from"), &JsValue::from_str(&normalize_address(&from_addr)?))?;
         }
         
         if let Some(to_addr) = to {
-            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("to"), &JsValue::from_str(&to_addr))?;
+            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("to"), &JsValue::from_str(&normalize_address(&to_addr)?))?;
         }
         
-        let filter = self.contract.create_event_filter("Transfer", indexed_params.into())?;
+        let filter = self.contract.create_event_filter("Transfer", indexed_params.into(), None)?;
         Ok(filter.into())
     }
 
@@ -274,14 +522,14 @@ impl ERC20Token {
         let indexed_params = js_sys::Object::new();
         
         if let Some(owner_addr) = owner {
-            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("owner"), &JsValue::from_str(&owner_addr))?;
+            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("owner"), &JsValue::from_str(&normalize_address(&owner_addr)?))?;
         }
         
         if let Some(spender_addr) = spender {
-            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("spender"), &JsValue::from_str(&spender_addr))?;
+            js_sys::Reflect::set(&indexed_params, &JsValue::from_str("spender"), &JsValue::from_str(&normalize_address(&spender_addr)?))?;
         }
         
-        let filter = self.contract.create_event_filter("Approval", indexed_params.into())?;
+        let filter = self.contract.create_event_filter("Approval", indexed_params.into(), None)?;
         Ok(filter.into())
     }
 
@@ -293,35 +541,22 @@ impl ERC20Token {
             None => self.decimals().await?,
         };
         
-        // Convert from hex if needed
-        let amount_str = if amount.starts_with("0x") {
-            match u128::from_str_radix(amount.trim_start_matches("0x"), 16) {
-                Ok(a) => a.to_string(),
-                Err(_) => return Err(JsValue::from_str("Invalid amount format")),
-            }
-        } else {
-            amount.to_string()
-        };
-        
-        // Ensure the amount string has at least decimal_places + 1 characters
-        let mut padded_amount = amount_str;
-        while padded_amount.len() <= decimal_places as usize {
-            padded_amount.insert(0, '0');
-        }
-        
-        // Insert decimal point
-        let len = padded_amount.len();
-        let decimal_pos = len - decimal_places as usize;
-        let formatted = format!(
-            "{}.{}",
-            &padded_amount[..decimal_pos],
-            &padded_amount[decimal_pos..]
-        );
-        
-        // Remove trailing zeros and decimal point if needed
-        let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
-        
-        Ok(trimmed.to_string())
+        crate::utils::format_units(amount, decimal_places)
+    }
+
+    /// Decodes a `Transfer` log of this token into `{ from, to, value, formatted }`, with
+    /// checksummed addresses, the raw value as a decimal string and `formatted` scaled by
+    /// the token's decimals
+    #[wasm_bindgen]
+    pub async fn decode_transfer_log(&self, log: JsValue) -> Result<JsValue, JsValue> {
+        self.decode_value_log(&log, "Transfer", ["from", "to"]).await
+    }
+
+    /// Decodes an `Approval` log of this token into `{ owner, spender, value, formatted }`,
+    /// like `decode_transfer_log`
+    #[wasm_bindgen]
+    pub async fn decode_approval_log(&self, log: JsValue) -> Result<JsValue, JsValue> {
+        self.decode_value_log(&log, "Approval", ["owner", "spender"]).await
     }
 
     /// Parse a human-readable token amount to the raw amount.
@@ -332,33 +567,160 @@ impl ERC20Token {
             None => self.decimals().await?,
         };
         
-        // Split the amount into whole and fractional parts
-        let parts: Vec<&str> = amount.split('.').collect();
-        let whole = parts[0].replace(',', "");
-        let fraction = if parts.len() > 1 { parts[1] } else { "" };
-        
-        // Ensure the fraction is not longer than the token's decimal places
-        if fraction.len() > decimal_places as usize {
-            return Err(JsValue::from_str("Too many decimal places"));
+        parse_amount(amount, decimal_places).map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl ERC20Token {
+    /// Decodes a `Transfer` or `Approval` log, whose inputs are two indexed addresses
+    /// followed by a value, into an object with the addresses under `address_keys`
+    async fn decode_value_log(&self, log: &JsValue, event_name: &str, address_keys: [&str; 2]) -> Result<JsValue, JsValue> {
+        let raw_log = event_log_from_js(log)?;
+        if !raw_log.address.is_empty() && !addresses_equal(&raw_log.address, &self.contract.address()) {
+            return Err(JsValue::from_str(&format!(
+                "Log was emitted by {}, not by this token ({})", raw_log.address, self.contract.address()
+            )));
         }
         
-        // Construct the raw amount
-        let mut raw_amount = whole;
-        
-        // Pad the fraction with zeros if needed
-        let mut padded_fraction = fraction.to_string();
-        while padded_fraction.len() < decimal_places as usize {
-            padded_fraction.push('0');
+        let (event, values) = self.contract.decode_event_log(&raw_log).map_err(|e| JsValue::from_str(&e))?;
+        if event.name != event_name {
+            return Err(JsValue::from_str(&format!("Expected a {} log, got {}", event_name, event.name)));
         }
         
-        raw_amount.push_str(&padded_fraction);
+        let (first, second, value) = match values.as_slice() {
+            [LogValue::Decoded(Token::Address(first)), LogValue::Decoded(Token::Address(second)), LogValue::Decoded(Token::Uint(value))] => {
+                (first, second, value.to_string())
+            },
+            _ => return Err(JsValue::from_str(&format!("Malformed {} log", event_name))),
+        };
         
-        // Remove leading zeros
-        raw_amount = raw_amount.trim_start_matches('0').to_string();
-        if raw_amount.is_empty() {
-            raw_amount = "0".to_string();
-        }
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str(address_keys[0]), &JsValue::from_str(&normalize_address(first)?))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str(address_keys[1]), &JsValue::from_str(&normalize_address(second)?))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("value"), &JsValue::from_str(&value))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("formatted"), &JsValue::from_str(&self.format_units(&value, None).await?))?;
         
-        Ok(raw_amount)
+        Ok(result.into())
+    }
+
+    /// Executes read-only calls in one round-trip: through multicall when configured,
+    /// otherwise as a batched `eth_call` request. Returns the raw return data per call.
+    async fn fetch_calls(&self, calls: &[(String, Vec<u8>)]) -> Result<Vec<Option<Vec<u8>>>, JsValue> {
+        if let Some(multicall_address) = &self.multicall_address {
+            return aggregate3(multicall_address, calls).await;
+        }
+
+        let requests = js_sys::Array::new();
+        for (target, data) in calls {
+            let call = js_sys::Object::new();
+            js_sys::Reflect::set(&call, &JsValue::from_str("to"), &JsValue::from_str(target))?;
+            js_sys::Reflect::set(&call, &JsValue::from_str("data"), &JsValue::from_str(&bytes_to_hex(data)))?;
+
+            let params = js_sys::Array::new();
+            params.push(&call);
+            params.push(&JsValue::from_str("latest"));
+
+            let request = js_sys::Object::new();
+            js_sys::Reflect::set(&request, &JsValue::from_str("method"), &JsValue::from_str("eth_call"))?;
+            js_sys::Reflect::set(&request, &JsValue::from_str("params"), &params)?;
+            requests.push(&request);
+        }
+
+        let responses = rpc_batch(requests.into()).await?;
+        Ok(js_sys::Array::from(&responses)
+            .iter()
+            .map(|response| response.as_string().and_then(|hex| hex_to_bytes(&hex).ok()))
+            .collect())
+    }
+}
+
+/// Converts a human-readable decimal amount to the raw integer amount for the given decimals.
+/// The amount must be a non-negative decimal with at most one decimal point; commas are
+/// only accepted as thousands separators in the whole part.
+fn parse_amount(amount: &str, decimal_places: u8) -> Result<String, String> {
+    if amount.is_empty() {
+        return Err("Invalid amount: empty string".to_string());
+    }
+    
+    if amount.starts_with('-') {
+        return Err(format!("Invalid amount '{}': negative amounts are not allowed", amount));
     }
-} 
\ No newline at end of file
+    
+    // Split the amount into whole and fractional parts
+    let parts: Vec<&str> = amount.split('.').collect();
+    if parts.len() > 2 {
+        return Err(format!("Invalid amount '{}': more than one decimal point", amount));
+    }
+    let whole = parts[0];
+    let fraction = if parts.len() > 1 { parts[1] } else { "" };
+    
+    // Commas must separate groups of exactly three digits
+    if whole.contains(',') {
+        let groups: Vec<&str> = whole.split(',').collect();
+        let valid_grouping = (1..=3).contains(&groups[0].len())
+            && groups[1..].iter().all(|group| group.len() == 3);
+        if !valid_grouping {
+            return Err(format!("Invalid amount '{}': misplaced thousands separator", amount));
+        }
+    }
+    let whole = whole.replace(',', "");
+    
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid amount '{}': only digits are allowed", amount));
+    }
+    
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(format!("Invalid amount '{}': no digits", amount));
+    }
+    
+    // Ensure the fraction is not longer than the token's decimal places
+    if fraction.len() > decimal_places as usize {
+        return Err(format!(
+            "Too many decimal places: '{}' has {} fractional digits but the token supports {}",
+            amount, fraction.len(), decimal_places
+        ));
+    }
+    
+    // Construct the raw amount
+    let mut raw_amount = whole;
+    
+    // Pad the fraction with zeros if needed
+    let mut padded_fraction = fraction.to_string();
+    while padded_fraction.len() < decimal_places as usize {
+        padded_fraction.push('0');
+    }
+    
+    raw_amount.push_str(&padded_fraction);
+    
+    // Remove leading zeros
+    raw_amount = raw_amount.trim_start_matches('0').to_string();
+    if raw_amount.is_empty() {
+        raw_amount = "0".to_string();
+    }
+    
+    Ok(raw_amount)
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_amount_rejects_malformed_input() {
+        assert!(parse_amount("1.2.3", 18).is_err());
+        assert!(parse_amount("-5", 18).is_err());
+        assert!(parse_amount("", 18).is_err());
+        assert!(parse_amount(".", 18).is_err());
+        assert!(parse_amount("abc", 18).is_err());
+        assert!(parse_amount("10,00.5", 18).is_err());
+        assert!(parse_amount("1.0000001", 6).is_err());
+    }
+
+    #[test]
+    fn parse_amount_accepts_thousands_separators() {
+        assert_eq!(parse_amount("1,000.5", 18).unwrap(), "1000500000000000000000");
+        assert_eq!(parse_amount("1,000.5", 6).unwrap(), "1000500000");
+        assert_eq!(parse_amount("0.000001", 6).unwrap(), "1");
+        assert_eq!(parse_amount("0", 18).unwrap(), "0");
+    }
+}
@@ -1,5 +1,10 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::codec::{encode, tokenize};
+use super::contract::{EventParameter, Parameter};
+use super::utils::{bytes_to_hex, hex_to_bytes, keccak256};
+use crate::bindings::EventOptions;
+use crate::utils::{block_number_to_tag, normalize_block_tag};
 
 /// Event data returned from Ethereum logs
 #[derive(Serialize, Deserialize, Clone)]
@@ -49,7 +54,18 @@ pub enum ParamType {
 impl ParamType {
     /// Convert a string type to a ParamType
     pub fn from_str(type_str: &str) -> Option<Self> {
-        if type_str == "address" {
+        if type_str.ends_with(']') {
+            let start_pos = type_str.rfind('[')?;
+            let element_type = ParamType::from_str(&type_str[..start_pos])?;
+            let size_str = &type_str[start_pos + 1..type_str.len() - 1];
+            
+            if size_str.is_empty() {
+                Some(ParamType::Array(Box::new(element_type)))
+            } else {
+                let size = size_str.parse::<usize>().ok()?;
+                Some(ParamType::FixedArray(Box::new(element_type), size))
+            }
+        } else if type_str == "address" {
             Some(ParamType::Address)
         } else if type_str == "bytes" {
             Some(ParamType::Bytes)
@@ -60,7 +76,7 @@ impl ParamType {
         } else if type_str.starts_with("uint") {
             let size_str = &type_str[4..];
             if let Ok(size) = size_str.parse::<usize>() {
-                if size % 8 == 0 && size <= 256 {
+                if size >= 8 && size % 8 == 0 && size <= 256 {
                     return Some(ParamType::Uint(size));
                 }
             }
@@ -68,7 +84,7 @@ impl ParamType {
         } else if type_str.starts_with("int") {
             let size_str = &type_str[3..];
             if let Ok(size) = size_str.parse::<usize>() {
-                if size % 8 == 0 && size <= 256 {
+                if size >= 8 && size % 8 == 0 && size <= 256 {
                     return Some(ParamType::Int(size));
                 }
             }
@@ -85,14 +101,91 @@ impl ParamType {
             None
         }
     }
+
+    /// Convert an ABI parameter to a ParamType, resolving tuple components
+    pub fn from_parameter(param: &Parameter) -> Option<Self> {
+        Self::from_type_and_components(&param.r#type, param.components.as_deref())
+    }
+
+    /// Convert an event parameter to a ParamType, resolving tuple components
+    pub fn from_event_parameter(param: &EventParameter) -> Option<Self> {
+        Self::from_type_and_components(&param.r#type, param.components.as_deref())
+    }
+
+    fn from_type_and_components(type_str: &str, components: Option<&[Parameter]>) -> Option<Self> {
+        match type_str.strip_prefix("tuple") {
+            Some(suffix) => {
+                let mut component_types = Vec::new();
+                for component in components.unwrap_or_default() {
+                    component_types.push(ParamType::from_parameter(component)?);
+                }
+                
+                // Re-apply any array dimensions that follow the tuple keyword
+                let mut param_type = ParamType::Tuple(component_types);
+                let mut dims = suffix;
+                while let Some(rest) = dims.strip_prefix('[') {
+                    let end_pos = rest.find(']')?;
+                    let size_str = &rest[..end_pos];
+                    param_type = if size_str.is_empty() {
+                        ParamType::Array(Box::new(param_type))
+                    } else {
+                        ParamType::FixedArray(Box::new(param_type), size_str.parse::<usize>().ok()?)
+                    };
+                    dims = &rest[end_pos + 1..];
+                }
+                
+                if dims.is_empty() { Some(param_type) } else { None }
+            },
+            None => ParamType::from_str(type_str),
+        }
+    }
+
+    /// Returns true if the type is dynamically sized in the ABI encoding
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(element_type, _) => element_type.is_dynamic(),
+            ParamType::Tuple(types) => types.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ParamType {
+    /// Formats the type in its canonical ABI form, e.g. `uint256[]` or `(address,bool)`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::Int(size) => write!(f, "int{}", size),
+            ParamType::Uint(size) => write!(f, "uint{}", size),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(element_type) => write!(f, "{}[]", element_type),
+            ParamType::FixedBytes(size) => write!(f, "bytes{}", size),
+            ParamType::FixedArray(element_type, size) => write!(f, "{}[{}]", element_type, size),
+            ParamType::Tuple(types) => {
+                let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+                write!(f, "({})", types.join(","))
+            },
+        }
+    }
 }
 
-/// Represents a filter for Ethereum events
+/// Represents a filter for Ethereum events.
+/// Topics are positional; a `None` topic matches any value at that position, and a
+/// position with several candidates matches any of them (additional event signatures
+/// do this for topic0, so one filter can match several events).
 #[wasm_bindgen]
 pub struct ContractEventFilter {
     event_signature: String,
     contract_address: String,
-    topics: Vec<String>,
+    topics: Vec<Option<Vec<String>>>,
+    /// Block bounds as normalized JSON-RPC block parameters (hex numbers or named tags)
+    from_block: Option<String>,
+    to_block: Option<String>,
+    /// Provider used instead of the default one from `get_provider`
+    provider: Option<JsValue>,
 }
 
 #[wasm_bindgen]
@@ -100,36 +193,159 @@ impl ContractEventFilter {
     /// Creates a new event filter for the given event signature and contract address
     #[wasm_bindgen(constructor)]
     pub fn new(event_signature: String, contract_address: String) -> ContractEventFilter {
-        let mut topics = Vec::new();
-        topics.push(event_signature.clone());
+        let topics = vec![Some(vec![event_signature.clone()])];
         
         ContractEventFilter {
             event_signature,
             contract_address,
             topics,
+            from_block: None,
+            to_block: None,
+            provider: None,
         }
     }
     
-    /// Adds a topic (indexed parameter) to the filter
+    /// Creates a filter without a signature topic, for anonymous events.
+    /// Topics added afterwards start at index 0.
     #[wasm_bindgen]
-    pub fn add_topic(&mut self, topic: String) -> Result<(), JsValue> {
-        if self.topics.len() >= 4 {
-            return Err(JsValue::from_str("Maximum 4 topics allowed"));
+    pub fn anonymous(contract_address: String) -> ContractEventFilter {
+        ContractEventFilter {
+            event_signature: String::new(),
+            contract_address,
+            topics: Vec::new(),
+            from_block: None,
+            to_block: None,
+            provider: None,
+        }
+    }
+    
+    /// Sets topic0 explicitly, e.g. to filter by a known topic instead of the computed signature
+    #[wasm_bindgen]
+    pub fn with_topic0(&mut self, topic0: String) {
+        match self.topics.first_mut() {
+            Some(first) => *first = Some(vec![topic0.clone()]),
+            None => self.topics.push(Some(vec![topic0.clone()])),
+        }
+        self.event_signature = topic0;
+    }
+    
+    /// Adds another event signature that topic0 may match, so one query returns
+    /// the logs of several events (e.g. both `Transfer` and `Approval`)
+    #[wasm_bindgen]
+    pub fn add_event_signature(&mut self, event_signature: String) -> Result<(), JsValue> {
+        let signatures = match self.topics.first_mut() {
+            Some(Some(signatures)) if !self.event_signature.is_empty() => signatures,
+            _ => return Err(JsValue::from_str("Cannot add an event signature to a filter without a signature topic")),
+        };
+        validate_topic(&event_signature)?;
+        
+        let signature = event_signature.to_lowercase();
+        if !signatures.iter().any(|s| s.to_lowercase() == signature) {
+            signatures.push(event_signature);
         }
-        self.topics.push(topic);
         Ok(())
     }
     
+    /// Creates a filter from `EventOptions`, carrying over its address, topics and block range.
+    /// The first topic is treated as the event signature.
+    #[wasm_bindgen]
+    pub fn from_event_options(opts: &EventOptions) -> Result<ContractEventFilter, JsValue> {
+        if opts.topics().len() > 4 {
+            return Err(JsValue::from_str("Maximum 4 topics allowed"));
+        }
+        for topic in opts.topics() {
+            validate_topic(topic)?;
+        }
+        
+        let mut filter = ContractEventFilter {
+            event_signature: opts.topics().first().cloned().unwrap_or_default(),
+            contract_address: opts.address().unwrap_or_default(),
+            topics: opts.topics().iter().map(|topic| Some(vec![topic.clone()])).collect(),
+            from_block: None,
+            to_block: None,
+            provider: None,
+        };
+        filter.apply_block_range(opts.from_block().map(block_number_to_tag), opts.to_block().map(block_number_to_tag))
+            .map_err(|e| JsValue::from_str(&e))?;
+        
+        Ok(filter)
+    }
+    
+    /// Restricts the filter to the given block range (inclusive). Each bound may be a block
+    /// number (number, decimal or hex string) or a tag such as "latest", "safe" or "finalized",
+    /// normalized like other block parameters; null or undefined leaves that bound open.
+    #[wasm_bindgen]
+    pub fn set_block_range(&mut self, from_block: JsValue, to_block: JsValue) -> Result<(), JsValue> {
+        let normalize = |block: &JsValue| -> Result<Option<String>, JsValue> {
+            if block.is_null() || block.is_undefined() {
+                return Ok(None);
+            }
+            normalize_block_tag(block).map(Some)
+        };
+        let from_block = normalize(&from_block)?;
+        let to_block = normalize(&to_block)?;
+        self.apply_block_range(from_block, to_block).map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// The normalized lower bound, e.g. `0x10` or `latest`
+    #[wasm_bindgen(getter)]
+    pub fn from_block(&self) -> Option<String> {
+        self.from_block.clone()
+    }
+    
+    /// The normalized upper bound, e.g. `0x20` or `finalized`
+    #[wasm_bindgen(getter)]
+    pub fn to_block(&self) -> Option<String> {
+        self.to_block.clone()
+    }
+    
+    /// Adds a topic (indexed parameter) to the filter: a 32-byte `0x` hex value,
+    /// or null to match any value at this position.
+    #[wasm_bindgen]
+    pub fn add_topic(&mut self, topic: Option<String>) -> Result<(), JsValue> {
+        match topic {
+            Some(topic) => self.add_topic_alternatives(vec![topic]),
+            None => self.push_topic(None),
+        }
+    }
+    
+    /// Adds a topic position that matches any of the given 32-byte `0x` hex values
+    #[wasm_bindgen]
+    pub fn add_topic_alternatives(&mut self, topics: Vec<String>) -> Result<(), JsValue> {
+        if topics.is_empty() {
+            return Err(JsValue::from_str("At least one topic is required; use add_topic(null) for a wildcard"));
+        }
+        for topic in &topics {
+            validate_topic(topic)?;
+        }
+        self.push_topic(Some(topics))
+    }
+    
     /// Converts the filter to a JS object that can be used with eth_getLogs or eth_subscribe
     #[wasm_bindgen]
     pub fn to_filter_object(&self) -> Result<JsValue, JsValue> {
         let filter = js_sys::Object::new();
         
-        js_sys::Reflect::set(&filter, &JsValue::from_str("address"), &JsValue::from_str(&self.contract_address))?;
+        if !self.contract_address.is_empty() {
+            js_sys::Reflect::set(&filter, &JsValue::from_str("address"), &JsValue::from_str(&self.contract_address))?;
+        }
+        if let Some(from_block) = &self.from_block {
+            js_sys::Reflect::set(&filter, &JsValue::from_str("fromBlock"), &JsValue::from_str(from_block))?;
+        }
+        if let Some(to_block) = &self.to_block {
+            js_sys::Reflect::set(&filter, &JsValue::from_str("toBlock"), &JsValue::from_str(to_block))?;
+        }
         
+        // Wildcards become null placeholders; trailing ones are dropped as they match anything
+        let topic_count = self.topics.iter().rposition(|topic| topic.is_some()).map_or(0, |i| i + 1);
         let topics_array = js_sys::Array::new();
-        for topic in &self.topics {
-            topics_array.push(&JsValue::from_str(topic));
+        for topic in &self.topics[..topic_count] {
+            match topic.as_deref() {
+                Some([topic]) => topics_array.push(&JsValue::from_str(topic)),
+                // Several candidates are matched with a nested array at that position
+                Some(candidates) => topics_array.push(&candidates.iter().map(|c| JsValue::from_str(c)).collect::<js_sys::Array>()),
+                None => topics_array.push(&JsValue::null()),
+            };
         }
         
         js_sys::Reflect::set(&filter, &JsValue::from_str("topics"), &topics_array)?;
@@ -137,40 +353,177 @@ impl ContractEventFilter {
         Ok(filter.into())
     }
     
-    /// Subscribes to events matching this filter
+    /// Fetches the logs matching this filter with eth_getLogs.
+    /// When the provider rejects the query because the block range is too wide or returns
+    /// too many results, the range is halved and each half fetched separately, up to
+    /// `max_depth` levels of splitting (default 12) before the provider's error is returned.
+    #[wasm_bindgen]
+    pub async fn get_logs(&self, max_depth: Option<u32>) -> Result<JsValue, JsValue> {
+        let max_depth = max_depth.unwrap_or(DEFAULT_LOG_SPLIT_DEPTH);
+        
+        let provider = self.provider()?;
+        let error = match query_logs(&provider, &self.to_filter_object()?).await {
+            Ok(logs) => return Ok(logs),
+            Err(error) if is_log_range_error(&error) => error,
+            Err(error) => return Err(error),
+        };
+        
+        // Resolve open bounds and tags to block numbers so the range can be split
+        let from_block = match &self.from_block {
+            Some(tag) => resolve_block_tag(&provider, tag).await?,
+            None => 0,
+        };
+        let to_block = match &self.to_block {
+            Some(tag) => resolve_block_tag(&provider, tag).await?,
+            None => crate::eth_integration::get_block_number_with_provider(&provider).await?,
+        };
+        if max_depth == 0 || from_block >= to_block {
+            return Err(error);
+        }
+        
+        // Depth-first over (from, to, depth) ranges; the upper half is pushed first
+        // so ranges are fetched, and their logs appended, in block order
+        let all_logs = js_sys::Array::new();
+        let mut ranges = vec![(from_block, to_block, 0u32)];
+        while let Some((from, to, depth)) = ranges.pop() {
+            let filter = self.to_filter_object()?;
+            js_sys::Reflect::set(&filter, &JsValue::from_str("fromBlock"), &JsValue::from_str(&block_number_to_tag(from)))?;
+            js_sys::Reflect::set(&filter, &JsValue::from_str("toBlock"), &JsValue::from_str(&block_number_to_tag(to)))?;
+            
+            match query_logs(&provider, &filter).await {
+                Ok(logs) => {
+                    for log in js_sys::Array::from(&logs).iter() {
+                        all_logs.push(&log);
+                    }
+                },
+                Err(error) if is_log_range_error(&error) && depth < max_depth && from < to => {
+                    let middle = from + (to - from) / 2;
+                    ranges.push((middle + 1, to, depth + 1));
+                    ranges.push((from, middle, depth + 1));
+                },
+                Err(error) => return Err(error),
+            }
+        }
+        
+        Ok(all_logs.into())
+    }
+    
+    /// Waits for the next log matching this filter, polling eth_getLogs until one appears
+    /// or `timeout_ms` elapses. Only blocks after the current one are searched, so logs
+    /// emitted before the call are never returned. Resolves with the raw log; use
+    /// `Contract::wait_for_event` to wait for a log and get it decoded.
+    #[wasm_bindgen]
+    pub async fn wait_for_next(&self, timeout_ms: u32) -> Result<JsValue, JsValue> {
+        let deadline = js_sys::Date::now() + timeout_ms as f64;
+        let provider = self.provider()?;
+        let mut next_block = crate::eth_integration::get_block_number_with_provider(&provider).await? + 1;
+        
+        loop {
+            let latest_block = crate::eth_integration::get_block_number_with_provider(&provider).await?;
+            if latest_block >= next_block {
+                let filter = self.to_filter_object()?;
+                js_sys::Reflect::set(&filter, &JsValue::from_str("fromBlock"), &JsValue::from_str(&block_number_to_tag(next_block)))?;
+                js_sys::Reflect::set(&filter, &JsValue::from_str("toBlock"), &JsValue::from_str(&block_number_to_tag(latest_block)))?;
+                
+                let logs = js_sys::Array::from(&query_logs(&provider, &filter).await?);
+                if logs.length() > 0 {
+                    return Ok(logs.get(0));
+                }
+                next_block = latest_block + 1;
+            }
+            
+            let remaining_ms = deadline - js_sys::Date::now();
+            if remaining_ms <= 0.0 {
+                return Err(JsValue::from_str(&format!("Timed out after {}ms waiting for a matching event", timeout_ms)));
+            }
+            crate::eth_integration::sleep_ms(remaining_ms.min(WAIT_FOR_EVENT_POLL_MS as f64) as u32).await?;
+        }
+    }
+    
+    /// Subscribes to events matching this filter, calling `callback(error, log, removed)`.
+    /// Every poll rescans the last `LOG_REORG_WINDOW` blocks with eth_getLogs, so each log is
+    /// delivered once even though the scanned ranges overlap. eth_getLogs only returns
+    /// canonical logs, so a delivered log that no longer appears in the rescanned range was
+    /// dropped by a reorg: it is passed to the callback again with `removed` set so consumers
+    /// can roll back state. Logs older than the window are forgotten.
     #[wasm_bindgen]
     pub async fn subscribe(&self, callback: &js_sys::Function) -> Result<JsValue, JsValue> {
         #[cfg(target_arch = "wasm32")]
         {
-            let window = web_sys::window().ok_or_else(|| JsValue::from_str("Could not access window"))?;
-            
-            if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-                return Err(JsValue::from_str("Ethereum provider not found"));
-            }
-
-            let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
+            let ethereum = self.provider()?;
             
             // Not all providers support eth_subscribe, so we'll use eth_getLogs with a polling mechanism
             let filter_obj = self.to_filter_object()?;
             
             // Set up an interval to poll for logs
             let closure = js_sys::Function::new_with_args(
-                "filter, ethereum, callback",
+                "filter, ethereum, callback, reorgWindow",
                 r#"
+                // Block bounds of the filter; tags other than "earliest" resolve to the latest block
+                const bound = (tag, latest) => {
+                    if (typeof tag === 'string' && tag.startsWith('0x')) return BigInt(tag);
+                    return tag === 'earliest' ? 0n : latest;
+                };
+                
+                // Logs delivered within the rescanned window, keyed by block hash and log index
+                const delivered = new Map();
+                let firstBlock = null;
+                let nextBlock = null;
+                let polling = false;
+                
                 async function pollLogs() {
+                    // Skip a tick while the previous poll is still running
+                    if (polling) return;
+                    polling = true;
                     try {
+                        const latest = BigInt(await ethereum.request({ method: 'eth_blockNumber', params: [] }));
+                        if (firstBlock === null) {
+                            firstBlock = bound(filter.fromBlock, latest);
+                            nextBlock = firstBlock;
+                        }
+                        
+                        // Rescan recent blocks too, so logs dropped by a reorg can be detected
+                        let from = nextBlock;
+                        const windowStart = latest - BigInt(reorgWindow) + 1n;
+                        if (windowStart < from) from = windowStart;
+                        if (from < firstBlock) from = firstBlock;
+                        let to = latest;
+                        if (filter.toBlock !== undefined && bound(filter.toBlock, latest) < to) {
+                            to = bound(filter.toBlock, latest);
+                        }
+                        if (from > to) return;
+                        
                         const logs = await ethereum.request({
                             method: 'eth_getLogs',
-                            params: [filter]
-                        });
+                            params: [{ ...filter, fromBlock: '0x' + from.toString(16), toBlock: '0x' + to.toString(16) }]
+                        }) || [];
+                        
+                        const current = new Map(logs.map((log) => [`${log.blockHash}:${log.logIndex}`, log]));
                         
-                        if (logs && logs.length > 0) {
-                            for (const log of logs) {
-                                callback(null, log);
+                        // Roll back vanished logs before delivering their replacements
+                        for (const [key, log] of delivered) {
+                            const blockNumber = BigInt(log.blockNumber);
+                            if (blockNumber < from) {
+                                // Below the rescanned range, so it can no longer be checked
+                                delivered.delete(key);
+                            } else if (blockNumber <= to && !current.has(key)) {
+                                delivered.delete(key);
+                                callback(null, { ...log, removed: true }, true);
                             }
                         }
+                        
+                        for (const [key, log] of current) {
+                            if (!delivered.has(key)) {
+                                delivered.set(key, log);
+                                callback(null, log, false);
+                            }
+                        }
+                        
+                        nextBlock = to + 1n;
                     } catch (error) {
                         callback(error, null);
+                    } finally {
+                        polling = false;
                     }
                 }
                 
@@ -185,12 +538,8 @@ impl ContractEventFilter {
                 "#
             );
             
-            let result = closure.call3(
-                &JsValue::null(),
-                &filter_obj,
-                &ethereum,
-                callback
-            )?;
+            let args = js_sys::Array::of4(&filter_obj, &ethereum, callback, &JsValue::from(LOG_REORG_WINDOW));
+            let result = closure.apply(&JsValue::null(), &args)?;
             
             Ok(result)
         }
@@ -219,4 +568,198 @@ impl ContractEventFilter {
         
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+impl ContractEventFilter {
+    /// Sends this filter's requests through the given provider instead of the default one
+    pub(crate) fn set_provider(&mut self, provider: Option<JsValue>) {
+        self.provider = provider;
+    }
+    
+    /// The provider this filter was created with, or the default one
+    fn provider(&self) -> Result<JsValue, JsValue> {
+        match &self.provider {
+            Some(provider) => Ok(provider.clone()),
+            None => crate::eth_integration::get_provider(),
+        }
+    }
+    
+    fn push_topic(&mut self, topic: Option<Vec<String>>) -> Result<(), JsValue> {
+        if self.topics.len() >= 4 {
+            return Err(JsValue::from_str("Maximum 4 topics allowed"));
+        }
+        self.topics.push(topic);
+        Ok(())
+    }
+}
+
+/// Checks that a topic is a 32-byte value written as `0x` followed by 64 hex digits
+fn validate_topic(topic: &str) -> Result<(), JsValue> {
+    let valid = topic.len() == 66
+        && topic.starts_with("0x")
+        && topic[2..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(JsValue::from_str(&format!(
+            "Invalid topic '{}': expected 0x followed by 64 hex digits (32 bytes)", topic
+        )));
+    }
+    Ok(())
+}
+
+/// Encodes a value as the 32-byte topic an indexed event parameter of `param_type` produces,
+/// e.g. to match a specific sender with `add_topic`. Addresses, integers, booleans and `bytesN`
+/// are padded to a word; `string` and `bytes` values are hashed with keccak256, as Solidity does.
+/// Integers may be decimal or hex, booleans `true`/`false`, and `bytes` values hex.
+#[wasm_bindgen]
+pub fn encode_topic(param_type: &str, value: &str) -> Result<String, JsValue> {
+    let parsed_type = ParamType::from_str(param_type)
+        .ok_or_else(|| JsValue::from_str(&format!("Unsupported parameter type '{}'", param_type)))?;
+    encode_indexed_value(&parsed_type, &JsValue::from_str(value))
+        .map_err(|e| JsValue::from_str(&format!("Invalid {} topic value '{}': {}", param_type, value, e)))
+}
+
+/// Encodes a value as an event topic. Static values are ABI-encoded into a single word,
+/// while strings and bytes are matched by the keccak256 hash stored in the topic.
+pub(crate) fn encode_indexed_value(param_type: &ParamType, value: &JsValue) -> Result<String, String> {
+    match param_type {
+        ParamType::String => {
+            let text = value.as_string().ok_or_else(|| "Expected a string".to_string())?;
+            Ok(bytes_to_hex(&keccak256(text.as_bytes())))
+        },
+        ParamType::Bytes => {
+            let text = value.as_string().ok_or_else(|| "Expected a hex string".to_string())?;
+            Ok(bytes_to_hex(&keccak256(&hex_to_bytes(&text)?)))
+        },
+        ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_) => {
+            Err(format!("Filtering by indexed {} values is not supported", param_type))
+        },
+        _ => Ok(bytes_to_hex(&encode(&[tokenize(param_type, value)?])?)),
+    }
+}
+
+impl ContractEventFilter {
+    /// Sets normalized block bounds, checking that numeric bounds are in order.
+    /// Named tags are only ordered once resolved, so they are not compared here.
+    fn apply_block_range(&mut self, from_block: Option<String>, to_block: Option<String>) -> Result<(), String> {
+        let from_number = from_block.as_deref().and_then(block_tag_number);
+        let to_number = to_block.as_deref().and_then(block_tag_number);
+        if let (Some(from), Some(to)) = (from_number, to_number) {
+            if from > to {
+                return Err(format!("fromBlock {} is greater than toBlock {}", from, to));
+            }
+        }
+        self.from_block = from_block;
+        self.to_block = to_block;
+        Ok(())
+    }
+}
+
+/// The block number of a normalized hex block parameter; None for named tags
+fn block_tag_number(tag: &str) -> Option<u64> {
+    u64::from_str_radix(tag.strip_prefix("0x")?, 16).ok()
+}
+
+/// Resolves a normalized block parameter to a block number, looking up named tags
+async fn resolve_block_tag(provider: &JsValue, tag: &str) -> Result<u64, JsValue> {
+    if let Some(number) = block_tag_number(tag) {
+        return Ok(number);
+    }
+    if tag == "earliest" {
+        return Ok(0);
+    }
+    
+    let params = js_sys::Array::of2(&JsValue::from_str(tag), &JsValue::FALSE);
+    let block = crate::eth_integration::request_with_provider(provider, "eth_getBlockByNumber", params, &|_| None).await?;
+    if block.is_null() || block.is_undefined() {
+        return Err(JsValue::from_str(&format!("Block '{}' was not found", tag)));
+    }
+    js_sys::Reflect::get(&block, &JsValue::from_str("number"))?
+        .as_string()
+        .and_then(|number| block_tag_number(&number))
+        .ok_or_else(|| JsValue::from_str(&format!("Could not resolve block tag '{}' to a block number", tag)))
+}
+
+/// How many recent blocks each subscription poll rescans to detect logs removed by reorgs
+#[cfg(target_arch = "wasm32")]
+const LOG_REORG_WINDOW: u32 = 12;
+
+/// How often wait_for_next polls for new blocks
+const WAIT_FOR_EVENT_POLL_MS: u32 = 2000;
+
+/// How many times get_logs may halve a block range before giving up
+const DEFAULT_LOG_SPLIT_DEPTH: u32 = 12;
+
+async fn query_logs(provider: &JsValue, filter: &JsValue) -> Result<JsValue, JsValue> {
+    let logs = crate::eth_integration::read_request_with_provider(provider, "eth_getLogs", js_sys::Array::of1(filter), &|_| None).await?;
+    if !js_sys::Array::is_array(&logs) {
+        return Err(JsValue::from_str("Invalid eth_getLogs result"));
+    }
+    Ok(logs)
+}
+
+/// Returns true if an eth_getLogs error means the range or result set was too large.
+/// Providers word this differently, e.g. Infura's "query returned more than 10000 results",
+/// Alchemy's "Log response size exceeded" and QuickNode's block range limits.
+fn is_log_range_error(error: &JsValue) -> bool {
+    // Infura reports these with the "limit exceeded" code
+    let code = js_sys::Reflect::get(error, &JsValue::from_str("rpcCode")).ok().and_then(|c| c.as_f64());
+    if code == Some(crate::eth_integration::errors::LIMIT_EXCEEDED as f64) {
+        return true;
+    }
+    
+    let message = error.as_string()
+        .or_else(|| js_sys::Reflect::get(error, &JsValue::from_str("message")).ok()?.as_string())
+        .unwrap_or_default()
+        .to_lowercase();
+    
+    [
+        "query returned more than",
+        "response size exceeded",
+        "block range",
+        "range is too large",
+        "range too large",
+        "too many results",
+        "limit exceeded",
+        "exceeds the limit",
+    ].iter().any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::codec::integer_token;
+    use primitive_types::U256;
+
+    #[test]
+    fn block_range_accepts_numbers_and_tags() {
+        let mut filter = ContractEventFilter::new(String::new(), String::new());
+        filter.apply_block_range(Some(block_number_to_tag(16)), Some(block_number_to_tag(32))).unwrap();
+        assert_eq!(filter.from_block().as_deref(), Some("0x10"));
+        assert_eq!(filter.to_block().as_deref(), Some("0x20"));
+        
+        filter.apply_block_range(Some("0x10".to_string()), Some("finalized".to_string())).unwrap();
+        assert_eq!(filter.to_block().as_deref(), Some("finalized"));
+        filter.apply_block_range(Some("safe".to_string()), None).unwrap();
+        assert_eq!(filter.from_block().as_deref(), Some("safe"));
+        assert_eq!(filter.to_block(), None);
+        
+        assert!(filter.apply_block_range(Some("0x20".to_string()), Some("0x10".to_string())).is_err());
+    }
+
+    #[test]
+    fn block_tag_numbers() {
+        assert_eq!(block_tag_number("0x10"), Some(16));
+        assert_eq!(block_tag_number("latest"), None);
+    }
+
+    #[test]
+    fn integer_types_need_a_size_from_8_to_256_bits() {
+        assert!(matches!(ParamType::from_str("uint8"), Some(ParamType::Uint(8))));
+        assert!(matches!(ParamType::from_str("int256"), Some(ParamType::Int(256))));
+        for invalid in ["uint0", "int0", "uint7", "int264"] {
+            assert!(ParamType::from_str(invalid).is_none(), "{} should be rejected", invalid);
+        }
+        assert!(integer_token(false, U256::one(), true, 0).is_err());
+        assert!(integer_token(false, U256::one(), false, 0).is_err());
+    }
+}
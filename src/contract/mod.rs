@@ -1,8 +1,9 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use crate::utils::hex_to_decimal;
 use std::collections::HashMap;
 use serde_wasm_bindgen;
+use crate::utils::{addresses_equal, apply_gas_buffer, checksum_address, normalize_address, normalize_block_tag, DEFAULT_GAS_BUFFER_PERCENT};
+use crate::error::RustWasmError;
 
 // Re-export submodules
 mod abi;
@@ -11,14 +12,21 @@ mod events;
 mod utils;
 mod contract;
 mod deploy;
+mod codec;
+mod multicall;
+mod pack;
+mod storage;
+mod eip712;
 
 // Re-export types and functions
 pub use abi::*;
 pub use erc20::*;
 pub use events::*;
 pub use utils::*;
-pub use contract::{Function, Event, Parameter, EventParameter, StateMutability};
+pub use contract::{Function, Event, CustomError, Parameter, EventParameter, StateMutability};
 pub use deploy::ContractDeployer;
+pub use codec::*;
+pub use storage::*;
 // Re-export the internal Contract as public Contract 
 pub use self::contract::Contract as ContractImpl;
 
@@ -29,34 +37,48 @@ pub struct Contract {
     address: String,
     abi: String,
     functions: HashMap<String, Function>,
+    /// Function selectors by function name, computed once when the ABI is parsed
+    selectors: HashMap<String, String>,
     events: HashMap<String, Event>,
+    errors: HashMap<String, CustomError>,
+    check_code: bool,
+    /// Provider used instead of the default one from `get_provider`
+    provider: Option<JsValue>,
 }
 
 #[wasm_bindgen]
 impl Contract {
-    /// Creates a new Contract instance from an ABI and address
+    /// Creates a new Contract instance from an ABI and address. The ABI may be a JSON ABI
+    /// array or a compiler artifact (Hardhat, Truffle, Foundry) with an `"abi"` field.
     #[wasm_bindgen(constructor)]
     pub fn new(address: String, abi: String) -> Result<Contract, JsValue> {
-        // Parse ABI
+        Self::new_with_code_check(address, abi, false)
+    }
+
+    /// Creates a Contract that, when `check_code` is set, verifies with eth_getCode that code
+    /// is deployed at its address before each call or transaction, so calling an address that
+    /// only exists on another network fails with a clear error instead of an empty result
+    #[wasm_bindgen]
+    pub fn new_with_code_check(address: String, abi: String, check_code: bool) -> Result<Contract, JsValue> {
+        let address = normalize_address(&address)?;
+
+        // Parse ABI, unwrapping it first when a compiler artifact was passed
+        let abi = normalize_abi_json(&abi).map_err(|e| JsValue::from(RustWasmError::AbiParse(e.to_string())))?;
         let abi_items: Vec<AbiItem> = match serde_json::from_str(&abi) {
             Ok(items) => items,
-            Err(e) => return Err(JsValue::from_str(&format!("Failed to parse ABI: {}", e))),
+            Err(e) => return Err(RustWasmError::AbiParse(e.to_string()).into()),
         };
 
         // Build function and event maps
         let mut functions = HashMap::new();
         let mut events = HashMap::new();
+        let mut errors = HashMap::new();
 
         for item in abi_items {
             match item.r#type.as_str() {
                 "function" => {
+                    let state_mutability = resolve_state_mutability(&item);
                     if let Some(name) = item.name {
-                        let state_mutability = match item.state_mutability.as_deref() {
-                            Some("view") => StateMutability::View,
-                            Some("pure") => StateMutability::Pure,
-                            Some("payable") => StateMutability::Payable,
-                            _ => StateMutability::Nonpayable,
-                        };
 
                         let inputs = item.inputs.unwrap_or_default().into_iter().map(|input| {
                             Parameter {
@@ -118,6 +140,25 @@ impl Contract {
                         });
                     }
                 },
+                "error" => {
+                    if let Some(name) = item.name {
+                        let inputs = item.inputs.unwrap_or_default().into_iter().map(|input| {
+                            Parameter {
+                                name: input.name,
+                                r#type: input.r#type,
+                                components: input.components.map(|comps| {
+                                    comps.into_iter().map(|c| Parameter {
+                                        name: c.name,
+                                        r#type: c.r#type,
+                                        components: None,
+                                    }).collect()
+                                }),
+                            }
+                        }).collect();
+
+                        errors.insert(name.clone(), CustomError { name, inputs });
+                    }
+                },
                 _ => {}, // Ignore other ABI item types
             }
         }
@@ -125,8 +166,133 @@ impl Contract {
         Ok(Contract {
             address,
             abi,
+            selectors: compute_selectors(&functions),
             functions,
             events,
+            errors,
+            check_code,
+            provider: None,
+        })
+    }
+
+    /// Creates a Contract that sends all its requests through the given EIP-1193 provider
+    /// (any object with a `request` method, e.g. from WalletConnect or the Coinbase Wallet SDK)
+    /// instead of `window.ethereum`
+    #[wasm_bindgen]
+    pub fn with_provider(address: String, abi: String, provider: JsValue) -> Result<Contract, JsValue> {
+        crate::eth_integration::check_provider(&provider)?;
+        let mut contract = Self::new(address, abi)?;
+        contract.provider = Some(provider);
+        Ok(contract)
+    }
+
+    /// Creates a Contract from an array of human-readable ABI fragments such as
+    /// `"function transfer(address to, uint256 amount) returns (bool)"` and
+    /// `"event Transfer(address indexed from, address indexed to, uint256 value)"`.
+    /// The equivalent JSON ABI is generated and available through `abi`.
+    #[wasm_bindgen]
+    pub fn from_human_readable(address: String, fragments: JsValue) -> Result<Contract, JsValue> {
+        let address = normalize_address(&address)?;
+        if !js_sys::Array::is_array(&fragments) {
+            return Err(RustWasmError::AbiParse("expected an array of fragment strings".to_string()).into());
+        }
+        
+        let mut functions = HashMap::new();
+        let mut events = HashMap::new();
+        let mut abi_items = Vec::new();
+        
+        for fragment in js_sys::Array::from(&fragments).iter() {
+            let fragment = fragment.as_string()
+                .ok_or_else(|| RustWasmError::AbiParse("fragments must be strings".to_string()))?;
+            
+            match parse_human_readable_item(&fragment).map_err(RustWasmError::AbiParse)? {
+                HumanReadableItem::Function(function) => {
+                    abi_items.push(serde_json::json!({
+                        "type": "function",
+                        "name": function.name,
+                        "inputs": function.inputs.iter().map(parameter_json).collect::<Vec<_>>(),
+                        "outputs": function.outputs.iter().map(parameter_json).collect::<Vec<_>>(),
+                        "stateMutability": function.state_mutability,
+                    }));
+                    functions.insert(function.name.clone(), function);
+                },
+                HumanReadableItem::Event(event) => {
+                    let inputs: Vec<_> = event.inputs.iter()
+                        .map(|input| {
+                            let mut json = parameter_json(&Parameter {
+                                name: input.name.clone(),
+                                r#type: input.r#type.clone(),
+                                components: input.components.clone(),
+                            });
+                            json["indexed"] = serde_json::Value::Bool(input.indexed);
+                            json
+                        })
+                        .collect();
+                    abi_items.push(serde_json::json!({
+                        "type": "event",
+                        "name": event.name,
+                        "inputs": inputs,
+                        "anonymous": event.anonymous,
+                    }));
+                    events.insert(event.name.clone(), event);
+                },
+                HumanReadableItem::Other => {},
+            }
+        }
+        
+        Ok(Contract {
+            address,
+            abi: serde_json::Value::Array(abi_items).to_string(),
+            selectors: compute_selectors(&functions),
+            functions,
+            events,
+            errors: HashMap::new(),
+            check_code: false,
+            provider: None,
+        })
+    }
+
+    /// Serializes the parsed contract (address, ABI, functions with their precomputed
+    /// selectors, events and errors) to JSON, e.g. to persist it in localStorage.
+    /// A custom provider set with `with_provider` is not included.
+    #[wasm_bindgen]
+    pub fn to_snapshot(&self) -> Result<String, JsValue> {
+        let snapshot = ContractSnapshot {
+            version: SNAPSHOT_VERSION,
+            address: self.address.clone(),
+            abi: self.abi.clone(),
+            functions: self.functions.clone(),
+            selectors: self.selectors.clone(),
+            events: self.events.clone(),
+            errors: self.errors.clone(),
+            check_code: self.check_code,
+        };
+        
+        serde_json::to_string(&snapshot)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Rebuilds a contract from `to_snapshot` output without parsing the ABI or
+    /// recomputing selectors. Snapshots from another format version are rejected.
+    #[wasm_bindgen]
+    pub fn from_snapshot(snapshot: &str) -> Result<Contract, JsValue> {
+        let snapshot: ContractSnapshot = serde_json::from_str(snapshot)
+            .map_err(|e| JsValue::from_str(&format!("Invalid contract snapshot: {}", e)))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(JsValue::from_str(&format!(
+                "Unsupported contract snapshot version {} (expected {})", snapshot.version, SNAPSHOT_VERSION
+            )));
+        }
+        
+        Ok(Contract {
+            address: normalize_address(&snapshot.address)?,
+            abi: snapshot.abi,
+            functions: snapshot.functions,
+            selectors: snapshot.selectors,
+            events: snapshot.events,
+            errors: snapshot.errors,
+            check_code: snapshot.check_code,
+            provider: None,
         })
     }
 
@@ -158,7 +324,172 @@ impl Contract {
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))?)
     }
 
-    /// Encodes a function call for the given function name and arguments
+    /// Returns the contract's functions, sorted by name, as
+    /// `{ name, signature, selector, inputs, outputs, stateMutability }` objects
+    #[wasm_bindgen]
+    pub fn functions(&self) -> Result<JsValue, JsValue> {
+        let mut functions: Vec<&Function> = self.functions.values().collect();
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        let infos: Vec<FunctionInfo> = functions.into_iter()
+            .map(|function| {
+                let input_types: Vec<String> = function.inputs.iter()
+                    .map(|input| canonical_type(&input.r#type, input.components.as_deref()))
+                    .collect();
+                FunctionInfo {
+                    name: &function.name,
+                    signature: get_function_signature(&function.name, &input_types),
+                    selector: self.selector_of(function),
+                    inputs: &function.inputs,
+                    outputs: &function.outputs,
+                    state_mutability: &function.state_mutability,
+                }
+            })
+            .collect();
+        
+        serde_wasm_bindgen::to_value(&infos)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Returns the contract's events, sorted by name, as
+    /// `{ name, signature, topic0, inputs, anonymous }` objects; each input has an `indexed` flag
+    #[wasm_bindgen]
+    pub fn events(&self) -> Result<JsValue, JsValue> {
+        let mut events: Vec<&Event> = self.events.values().collect();
+        events.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        let infos: Vec<EventInfo> = events.into_iter()
+            .map(|event| {
+                let input_types: Vec<String> = event.inputs.iter()
+                    .map(|input| canonical_type(&input.r#type, input.components.as_deref()))
+                    .collect();
+                EventInfo {
+                    name: &event.name,
+                    signature: get_event_signature(&event.name, &input_types),
+                    topic0: (!event.anonymous).then(|| compute_event_signature(&event.name, &event.inputs)),
+                    inputs: &event.inputs,
+                    anonymous: event.anonymous,
+                }
+            })
+            .collect();
+        
+        serde_wasm_bindgen::to_value(&infos)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Looks up a function by its 4-byte selector (e.g. `0xa9059cbb`)
+    #[wasm_bindgen]
+    pub fn function_by_selector(&self, selector: &str) -> Result<JsValue, JsValue> {
+        let function = self.find_function_by_selector(selector)
+            .ok_or_else(|| JsValue::from_str(&format!("No function found for selector '{}'", selector)))?;
+        
+        serde_wasm_bindgen::to_value(function)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Decodes calldata into the called function's name and its named arguments
+    #[wasm_bindgen]
+    pub fn decode_calldata(&self, data: &str) -> Result<JsValue, JsValue> {
+        let bytes = hex_to_bytes(data).map_err(|e| JsValue::from_str(&e))?;
+        if bytes.len() < 4 {
+            return Err(JsValue::from_str("Calldata is shorter than a 4-byte selector"));
+        }
+        
+        let selector = bytes_to_hex(&bytes[..4]);
+        let function = self.find_function_by_selector(&selector)
+            .ok_or_else(|| match known_function_name(&selector) {
+                Some(signature) => JsValue::from_str(&format!(
+                    "No function found for selector '{}' (known signature: {})", selector, signature
+                )),
+                None => JsValue::from_str(&format!("No function found for selector '{}'", selector)),
+            })?;
+        
+        let mut types = Vec::new();
+        for input in &function.inputs {
+            let param_type = ParamType::from_parameter(input)
+                .ok_or_else(|| JsValue::from_str(&format!("Unsupported parameter type '{}'", input.r#type)))?;
+            types.push(param_type);
+        }
+        
+        let tokens = decode(&types, &bytes[4..]).map_err(|e| JsValue::from_str(&e))?;
+        
+        // Unnamed parameters are keyed by their position
+        let args = js_sys::Object::new();
+        for (i, (input, token)) in function.inputs.iter().zip(tokens.iter()).enumerate() {
+            let key = if input.name.is_empty() { i.to_string() } else { input.name.clone() };
+            js_sys::Reflect::set(&args, &JsValue::from_str(&key), &token.to_js_value())?;
+        }
+        
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("functionName"), &JsValue::from_str(&function.name))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("args"), &args)?;
+        
+        Ok(result.into())
+    }
+
+    /// Decodes revert data into `{ errorName, args }`. The selector is matched against the
+    /// custom errors in the ABI first, then the built-in `Error(string)` (args `{ reason }`)
+    /// and `Panic(uint256)` (args `{ code }`).
+    #[wasm_bindgen]
+    pub fn decode_error(&self, data: &str) -> Result<JsValue, JsValue> {
+        let bytes = hex_to_bytes(data).map_err(|e| JsValue::from_str(&e))?;
+        if bytes.len() < 4 {
+            return Err(JsValue::from_str("Revert data is shorter than a 4-byte selector"));
+        }
+        
+        let args = js_sys::Object::new();
+        let error_name = if let Some((error, tokens)) = self.decode_custom_error(&bytes) {
+            // Unnamed parameters are keyed by their position
+            for (i, (input, token)) in error.inputs.iter().zip(tokens.iter()).enumerate() {
+                let key = if input.name.is_empty() { i.to_string() } else { input.name.clone() };
+                js_sys::Reflect::set(&args, &JsValue::from_str(&key), &token.to_js_value())?;
+            }
+            error.name.clone()
+        } else {
+            let (name, key, param_type) = match bytes[..4] {
+                [0x08, 0xc3, 0x79, 0xa0] => ("Error", "reason", ParamType::String),
+                [0x4e, 0x48, 0x7b, 0x71] => ("Panic", "code", ParamType::Uint(256)),
+                _ => return Err(JsValue::from_str(&format!("No error found for selector '{}'", bytes_to_hex(&bytes[..4])))),
+            };
+            let token = decode(&[param_type], &bytes[4..]).map_err(|e| JsValue::from_str(&e))?.remove(0);
+            js_sys::Reflect::set(&args, &JsValue::from_str(key), &token.to_js_value())?;
+            name.to_string()
+        };
+        
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("errorName"), &JsValue::from_str(&error_name))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("args"), &args)?;
+        
+        Ok(result.into())
+    }
+
+    /// Fetches a transaction with eth_getTransactionByHash and decodes its input into
+    /// `{ functionName, args }` like `decode_calldata`. Errors if the transaction was not
+    /// sent to this contract or calls a function that is not in the ABI.
+    #[wasm_bindgen]
+    pub async fn decode_transaction(&self, tx_hash: String) -> Result<JsValue, JsValue> {
+        let tx = self.request("eth_getTransactionByHash", js_sys::Array::of1(&JsValue::from_str(&tx_hash))).await?;
+        if tx.is_null() || tx.is_undefined() {
+            return Err(JsValue::from_str(&format!("Transaction {} not found", tx_hash)));
+        }
+        
+        // `to` is null for contract creations
+        let to = js_sys::Reflect::get(&tx, &JsValue::from_str("to"))?.as_string().unwrap_or_default();
+        if !addresses_equal(&to, &self.address) {
+            return Err(JsValue::from_str(&format!(
+                "Transaction {} was sent to {} rather than this contract ({})",
+                tx_hash, if to.is_empty() { "no address" } else { &to }, self.address
+            )));
+        }
+        
+        let input = js_sys::Reflect::get(&tx, &JsValue::from_str("input"))?.as_string()
+            .ok_or_else(|| JsValue::from_str("Transaction has no input data"))?;
+        self.decode_calldata(&input)
+    }
+
+    /// Encodes a function call for the given function name and arguments.
+    /// Tuple (struct) arguments may be positional arrays or objects keyed by component name,
+    /// e.g. `[{ maker, taker, amount }, deadline]`; a missing field is an error.
     #[wasm_bindgen]
     pub fn encode_function_call(&self, function_name: &str, args: JsValue) -> Result<String, JsValue> {
         // Check if function exists
@@ -178,27 +509,494 @@ impl Contract {
             args => args,
         };
 
-        // For now, we're using a simplified encoding approach
-        // In a real implementation, we would use proper ABI encoding
-        let selector = compute_function_selector(function_name, &function.inputs);
+        let mut tokens = Vec::with_capacity(args_vec.len());
+        for (input, arg) in function.inputs.iter().zip(args_vec.iter()) {
+            let param_type = ParamType::from_parameter(input)
+                .ok_or_else(|| JsValue::from_str(&format!("Unsupported parameter type '{}'", input.r#type)))?;
+            let token = positional_tuple_value(input, arg)
+                .and_then(|arg| tokenize(&param_type, &arg))
+                .map_err(|e| JsValue::from_str(&format!("Invalid argument '{}' for '{}': {}", input.name, function_name, e)))?;
+            tokens.push(token);
+        }
         
-        // Encode arguments (simplified for demo)
-        let mut encoded_args = String::new();
-        for (i, arg) in args_vec.iter().enumerate() {
-            if let Some(arg_str) = arg.as_string() {
-                encoded_args.push_str(&format!("_{}", arg_str.replace(" ", "")));
-            } else {
-                // Handle non-string arguments
-                encoded_args.push_str(&format!("_{}", i));
+        let encoded_args = encode(&tokens).map_err(|e| JsValue::from_str(&e))?;
+        let selector = self.selector_of(function);
+        
+        Ok(format!("{}{}", selector, &bytes_to_hex(&encoded_args)[2..]))
+    }
+
+    /// Checks a function's arguments without encoding them: the argument count, and that
+    /// each value fits its declared type (address shape, `uintN`/`intN` range, booleans,
+    /// `bytesN` length, array lengths). On failure the error is an array of
+    /// `{ index, name, type, message }` objects, one per invalid argument, for inline form feedback.
+    #[wasm_bindgen]
+    pub fn validate_args(&self, function_name: &str, args: JsValue) -> Result<(), JsValue> {
+        let function = self.functions.get(function_name)
+            .ok_or_else(|| JsValue::from_str(&format!("Function '{}' not found in ABI", function_name)))?;
+        
+        let mut errors = Vec::new();
+        if !js_sys::Array::is_array(&args) {
+            errors.push(ArgumentError::general("Arguments must be an array".to_string()));
+        } else {
+            let args_vec = js_sys::Array::from(&args).to_vec();
+            if args_vec.len() != function.inputs.len() {
+                errors.push(ArgumentError::general(format!(
+                    "Expected {} arguments for function '{}', got {}",
+                    function.inputs.len(), function_name, args_vec.len()
+                )));
+            }
+            
+            for (index, (input, arg)) in function.inputs.iter().zip(args_vec.iter()).enumerate() {
+                let result = ParamType::from_parameter(input)
+                    .ok_or_else(|| format!("Unsupported parameter type '{}'", input.r#type))
+                    .and_then(|param_type| tokenize(&param_type, &positional_tuple_value(input, arg)?));
+                if let Err(message) = result {
+                    errors.push(ArgumentError {
+                        index: Some(index),
+                        name: input.name.clone(),
+                        r#type: canonical_type(&input.r#type, input.components.as_deref()),
+                        message,
+                    });
+                }
             }
         }
-
-        Ok(format!("{}{}", selector, encoded_args))
+        
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(serde_wasm_bindgen::to_value(&errors)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))?)
     }
 
     /// Calls a read-only (view/pure) function on the contract
     #[wasm_bindgen]
     pub async fn call(&self, function_name: &str, args: JsValue) -> Result<JsValue, JsValue> {
+        self.call_at_block(function_name, args, JsValue::UNDEFINED).await
+    }
+
+    /// Calls a read-only (view/pure) function on the contract at the given block.
+    /// The block may be a block number or a tag such as "latest", "pending" or "finalized".
+    #[wasm_bindgen]
+    pub async fn call_at_block(&self, function_name: &str, args: JsValue, block_tag: JsValue) -> Result<JsValue, JsValue> {
+        let (function, raw) = self.call_function(function_name, args, block_tag).await?;
+        
+        // Parse the result based on the function's output types
+        decode_function_result(function, JsValue::from_str(&raw))
+    }
+
+    /// Calls a read-only function like `call_at_block`, but returns `{ decoded, raw }` with
+    /// the raw hex return data next to the decoded value. Useful for debugging when the ABI's
+    /// output types don't match what the contract actually returns.
+    #[wasm_bindgen]
+    pub async fn call_raw_decoded(&self, function_name: &str, args: JsValue, block_tag: JsValue) -> Result<JsValue, JsValue> {
+        let (function, raw) = self.call_function(function_name, args, block_tag).await?;
+        let decoded = decode_function_result(function, JsValue::from_str(&raw))?;
+        
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("decoded"), &decoded)?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("raw"), &JsValue::from_str(&raw))?;
+        
+        Ok(result.into())
+    }
+
+    /// Sends pre-encoded `0x` calldata to the contract with eth_call and returns the raw hex
+    /// result without decoding. The block defaults to "latest" and may be a tag or a block number.
+    #[wasm_bindgen]
+    pub async fn call_raw(&self, data: &str, block_tag: JsValue) -> Result<String, JsValue> {
+        hex_to_bytes(data).map_err(|e| JsValue::from_str(&format!("Invalid calldata: {}", e)))?;
+        
+        let block_tag = normalize_block_tag(&block_tag)?;
+        
+        self.eth_call(data, &block_tag).await
+    }
+
+    /// Reads the raw 32-byte word in one of the contract's storage slots with eth_getStorageAt.
+    /// The slot may be decimal or hex (see `mapping_slot` for mapping entries); the block
+    /// defaults to "latest" and may be a tag or a block number.
+    #[wasm_bindgen]
+    pub async fn get_storage_at(&self, slot: &str, block_tag: JsValue) -> Result<String, JsValue> {
+        let slot = parse_slot(slot).map_err(|e| JsValue::from_str(&e))?;
+        let block_tag = normalize_block_tag(&block_tag)?;
+        
+        let params = js_sys::Array::new();
+        params.push(&JsValue::from_str(&self.address));
+        params.push(&JsValue::from_str(&format!("0x{:x}", slot)));
+        params.push(&JsValue::from_str(&block_tag));
+        
+        self.request("eth_getStorageAt", params).await?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Invalid eth_getStorageAt result"))
+    }
+
+    /// Returns the checksummed implementation address of an EIP-1967 proxy, read from the
+    /// implementation slot. Errors if the slot is empty, i.e. the contract is not such a proxy.
+    #[wasm_bindgen]
+    pub async fn get_implementation(&self) -> Result<String, JsValue> {
+        self.read_proxy_address(EIP1967_IMPLEMENTATION_SLOT, "implementation").await
+    }
+
+    /// Returns the checksummed address of the contract that holds this proxy's logic: the
+    /// implementation embedded in an EIP-1167 minimal proxy's code, otherwise the one in the
+    /// EIP-1967 implementation slot. Errors if the contract is neither kind of proxy.
+    #[wasm_bindgen]
+    pub async fn resolve_proxy_target(&self) -> Result<String, JsValue> {
+        let code = self.request("eth_getCode", js_sys::Array::of2(&JsValue::from_str(&self.address), &JsValue::from_str("latest"))).await?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Invalid eth_getCode result"))?;
+        if let Some(implementation) = is_minimal_proxy(&code) {
+            return Ok(implementation);
+        }
+        
+        // Only an empty slot means "not a proxy"; provider and RPC errors are passed on
+        self.read_proxy_slot(EIP1967_IMPLEMENTATION_SLOT).await?
+            .ok_or_else(|| JsValue::from_str(&format!("Contract {} is not an EIP-1167 or EIP-1967 proxy", self.address)))
+    }
+
+    /// Checks whether the contract implements an interface via ERC-165, calling
+    /// `supportsInterface(bytes4)` with `interface_id` (e.g. `0x80ac58cd` for ERC-721); the
+    /// function need not be in the ABI. Contracts without ERC-165 revert or return no data,
+    /// so both give false rather than an error.
+    #[wasm_bindgen]
+    pub async fn supports_interface(&self, interface_id: &str) -> Result<bool, JsValue> {
+        let id = hex_to_bytes(interface_id).ok()
+            .filter(|id| id.len() == 4 && strip_hex_prefix(interface_id).is_some())
+            .ok_or_else(|| JsValue::from_str(&format!("Invalid interface ID '{}': expected 0x followed by 4 bytes of hex", interface_id)))?;
+        
+        // supportsInterface(bytes4) shares its selector with the ERC-165 interface ID
+        let mut calldata = hex_to_bytes(INTERFACE_ID_ERC165).map_err(|e| JsValue::from_str(&e))?;
+        calldata.extend_from_slice(&id);
+        calldata.resize(4 + 32, 0);
+        
+        let result = match self.eth_call(&bytes_to_hex(&calldata), "latest").await {
+            Ok(result) => result,
+            Err(error) => {
+                let code = js_sys::Reflect::get(&error, &JsValue::from_str("code")).ok().and_then(|code| code.as_string());
+                return match code.as_deref() {
+                    Some("REVERTED") => Ok(false),
+                    _ => Err(error),
+                };
+            },
+        };
+        
+        // Anything other than an ABI-encoded `true` counts as unsupported
+        let word = hex_to_bytes(&result).map_err(|e| JsValue::from_str(&e))?;
+        Ok(word.len() >= 32 && word[31] == 1 && word[..31].iter().all(|byte| *byte == 0))
+    }
+
+    /// Returns the checksummed admin address of an EIP-1967 proxy, read from the admin slot.
+    /// Errors if the slot is empty, e.g. for proxies without an admin such as UUPS proxies.
+    #[wasm_bindgen]
+    pub async fn get_admin(&self) -> Result<String, JsValue> {
+        self.read_proxy_address(EIP1967_ADMIN_SLOT, "admin").await
+    }
+
+    /// Sends a transaction to execute a state-changing (nonpayable/payable) function on the contract.
+    /// A `timeout` option (milliseconds, `0` for none) overrides `set_request_timeout` for this call.
+    #[wasm_bindgen]
+    pub async fn send_transaction(&self, function_name: &str, args: JsValue, options: JsValue) -> Result<String, JsValue> {
+        let tx_options = self.prepare_transaction(function_name, args, &options).await?;
+        self.submit_transaction(tx_options, timeout_option(&options)?).await
+    }
+
+    /// Calls a payable function, sending `value` wei (a number or a decimal/hex string)
+    /// along with it. Any `value` in `options` is overridden; the options object is not modified.
+    #[wasm_bindgen]
+    pub async fn send_payable(&self, function_name: &str, args: JsValue, value: JsValue, options: JsValue) -> Result<String, JsValue> {
+        let function = self.functions.get(function_name)
+            .ok_or_else(|| JsValue::from_str(&format!("Function '{}' not found in ABI", function_name)))?;
+        if function.state_mutability != StateMutability::Payable {
+            return Err(JsValue::from_str(&format!("Function '{}' is not payable", function_name)));
+        }
+        
+        let payable_options = js_sys::Object::new();
+        if options.is_object() {
+            js_sys::Object::assign(&payable_options, &js_sys::Object::from(options));
+        }
+        js_sys::Reflect::set(&payable_options, &JsValue::from_str("value"), &value)?;
+        
+        self.send_transaction(function_name, args, payable_options.into()).await
+    }
+
+    /// Sends a transaction like `send_transaction`, estimating the gas limit when the options
+    /// do not set `gas` or `gasLimit`. The estimate is raised by `gas_buffer_percent`
+    /// (0-100, default 20) so small state changes between estimation and inclusion don't
+    /// make the transaction run out of gas.
+    #[wasm_bindgen]
+    pub async fn send_transaction_with_gas_estimate(
+        &self,
+        function_name: &str,
+        args: JsValue,
+        options: JsValue,
+        gas_buffer_percent: Option<u32>
+    ) -> Result<String, JsValue> {
+        let gas_buffer_percent = gas_buffer_percent.unwrap_or(DEFAULT_GAS_BUFFER_PERCENT);
+        let tx_options = self.prepare_transaction(function_name, args, &options).await?;
+        
+        if js_sys::Reflect::get(&tx_options, &JsValue::from_str("gas"))?.is_undefined() {
+            let estimate = self.request("eth_estimateGas", js_sys::Array::of1(&tx_options)).await?;
+            let estimate = estimate.as_string()
+                .and_then(|gas| u64::from_str_radix(gas.trim_start_matches("0x"), 16).ok())
+                .ok_or_else(|| JsValue::from_str("Invalid gas estimate result"))?;
+            
+            let gas_limit = apply_gas_buffer(estimate, gas_buffer_percent)?;
+            js_sys::Reflect::set(&tx_options, &JsValue::from_str("gas"), &JsValue::from_str(&format!("0x{:x}", gas_limit)))?;
+        }
+        
+        self.submit_transaction(tx_options, timeout_option(&options)?).await
+    }
+
+    /// Sends a transaction and waits for it to be mined with the given number of confirmations.
+    /// Returns `{ transactionHash, status, blockNumber, gasUsed, events }` where `events` holds
+    /// the decoded logs this contract emitted; reverted transactions carry a `revertReason`
+    /// when it can be recovered.
+    #[wasm_bindgen]
+    pub async fn send_and_wait(&self, function_name: &str, args: JsValue, options: JsValue, confirmations: u32) -> Result<JsValue, JsValue> {
+        let tx_hash = self.send_transaction(function_name, args, options).await?;
+        let provider = self.provider()?;
+        let receipt = crate::eth_integration::wait_for_receipt(&provider, &tx_hash, confirmations, &Default::default()).await?;
+        
+        let result = crate::eth_integration::receipt_status(&provider, &tx_hash, &receipt).await?;
+        let events = self.decode_own_logs(&receipt)?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("events"), &events)?;
+        
+        Ok(result.into())
+    }
+
+    /// Creates an EIP-2930 access list for a function call via eth_createAccessList.
+    /// Returns the provider's `{ accessList, gasUsed }` object; the access list can be
+    /// passed back as the `accessList` option of `send_transaction`.
+    #[wasm_bindgen]
+    pub async fn create_access_list(&self, function_name: &str, args: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+        if !self.functions.contains_key(function_name) {
+            return Err(JsValue::from_str(&format!("Function '{}' not found in ABI", function_name)));
+        }
+        
+        let encoded_call = self.encode_function_call(function_name, args)?;
+        let tx_object = self.build_tx_object(&encoded_call, &options)?;
+        
+        let params = js_sys::Array::new();
+        params.push(&tx_object);
+        params.push(&JsValue::from_str("latest"));
+        
+        let result = self.request("eth_createAccessList", params).await?;
+        
+        // Some nodes report a failing call through an `error` field instead of rejecting
+        let error = js_sys::Reflect::get(&result, &JsValue::from_str("error"))?;
+        if !error.is_undefined() && !error.is_null() {
+            return Err(JsValue::from_str(&format!(
+                "eth_createAccessList failed: {}", error.as_string().unwrap_or_default()
+            )));
+        }
+        
+        let access_list = js_sys::Object::new();
+        js_sys::Reflect::set(&access_list, &JsValue::from_str("accessList"), &js_sys::Reflect::get(&result, &JsValue::from_str("accessList"))?)?;
+        js_sys::Reflect::set(&access_list, &JsValue::from_str("gasUsed"), &js_sys::Reflect::get(&result, &JsValue::from_str("gasUsed"))?)?;
+        
+        Ok(access_list.into())
+    }
+
+    /// Decodes a log (an object with `topics` and `data`) emitted by this contract into
+    /// `{ eventName, args, removed }`, with args keyed by parameter name (or position when unnamed).
+    /// `removed` is true when the provider reports the log as dropped by a chain reorg.
+    /// Indexed parameters are read from the topics and the rest from the data, in declaration
+    /// order. Array and tuple parameters in the data (e.g. `uint256[] amounts`) are decoded
+    /// with the same decoder as function results, into nested JS arrays. Indexed dynamic
+    /// values are only stored as their hash, so they are returned as `{ hashed: true, value: "0x..." }`.
+    #[wasm_bindgen]
+    pub fn decode_log(&self, log: JsValue) -> Result<JsValue, JsValue> {
+        let raw_log = event_log_from_js(&log)?;
+        let (event, values) = self.decode_event_log(&raw_log).map_err(|e| JsValue::from_str(&e))?;
+        
+        let args = js_sys::Object::new();
+        for (i, (input, value)) in event.inputs.iter().zip(values.iter()).enumerate() {
+            let value = match value {
+                LogValue::Decoded(token) => token.to_js_value(),
+                LogValue::Hashed(topic) => {
+                    let hashed = js_sys::Object::new();
+                    js_sys::Reflect::set(&hashed, &JsValue::from_str("hashed"), &JsValue::from_bool(true))?;
+                    js_sys::Reflect::set(&hashed, &JsValue::from_str("value"), &JsValue::from_str(&bytes_to_hex(topic)))?;
+                    hashed.into()
+                },
+            };
+            
+            let key = if input.name.is_empty() { i.to_string() } else { input.name.clone() };
+            js_sys::Reflect::set(&args, &JsValue::from_str(&key), &value)?;
+        }
+        
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("eventName"), &JsValue::from_str(&event.name))?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("args"), &args)?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("removed"), &JsValue::from_bool(raw_log.removed.unwrap_or(false)))?;
+        
+        Ok(result.into())
+    }
+
+    /// Decodes the logs of a transaction receipt that were emitted by this contract into
+    /// an array of `EventData` objects. Logs from other addresses, or of events not in the
+    /// ABI, are skipped.
+    #[wasm_bindgen]
+    pub fn decode_receipt_logs(&self, receipt: JsValue) -> Result<JsValue, JsValue> {
+        let mut events = Vec::new();
+        for log in self.own_logs(&receipt)? {
+            let raw_log = event_log_from_js(&log)?;
+            let (event, values) = match self.decode_event_log(&raw_log) {
+                Ok(decoded) => decoded,
+                Err(_) => continue,
+            };
+            
+            let args = event.inputs.iter().zip(values).enumerate()
+                .map(|(i, (input, value))| LogParam {
+                    name: if input.name.is_empty() { i.to_string() } else { input.name.clone() },
+                    value: match value {
+                        LogValue::Decoded(token) => token.to_decimal_string(),
+                        LogValue::Hashed(topic) => bytes_to_hex(&topic),
+                    },
+                    r#type: ParamType::from_event_parameter(input).unwrap_or(ParamType::Bytes),
+                })
+                .collect();
+            
+            events.push(EventData {
+                event_name: event.name.clone(),
+                args,
+                raw_log,
+            });
+        }
+        
+        serde_wasm_bindgen::to_value(&events)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Creates a new event subscription for the specified event.
+    /// `indexed_params` maps indexed parameter names to the values to match; parameters
+    /// that are left out match any value. `topic0_override` replaces the computed event
+    /// signature as topic0. Anonymous events have no topic0, so their indexed parameters
+    /// start at topic index 0. The filter queries logs through this contract's provider.
+    #[wasm_bindgen]
+    pub fn create_event_filter(&self, event_name: &str, indexed_params: JsValue, topic0_override: Option<String>) -> Result<ContractEventFilter, JsValue> {
+        // Check if event exists
+        let event = match self.events.get(event_name) {
+            Some(e) => e,
+            None => return Err(JsValue::from_str(&format!("Event '{}' not found in ABI", event_name))),
+        };
+
+        // Create a new event filter
+        let mut filter = if event.anonymous {
+            ContractEventFilter::anonymous(self.address.clone())
+        } else {
+            ContractEventFilter::new(compute_event_signature(event_name, &event.inputs), self.address.clone())
+        };
+        filter.set_provider(self.provider.clone());
+        if let Some(topic0) = topic0_override {
+            filter.with_topic0(topic0);
+        }
+        
+        // Every indexed parameter takes a topic position, so skipped ones are added as wildcards
+        let has_params = !indexed_params.is_null() && !indexed_params.is_undefined();
+        for param in event.inputs.iter().filter(|p| p.indexed) {
+            let value = if has_params {
+                js_sys::Reflect::get(&indexed_params, &JsValue::from_str(&param.name))?
+            } else {
+                JsValue::UNDEFINED
+            };
+            
+            if value.is_null() || value.is_undefined() {
+                filter.add_topic(None)?;
+            } else {
+                let param_type = ParamType::from_event_parameter(param)
+                    .ok_or_else(|| JsValue::from_str(&format!("Unsupported parameter type '{}'", param.r#type)))?;
+                let topic = encode_indexed_value(&param_type, &value)
+                    .map_err(|e| JsValue::from_str(&format!("Invalid value for indexed parameter '{}': {}", param.name, e)))?;
+                filter.add_topic(Some(topic))?;
+            }
+        }
+        
+        Ok(filter)
+    }
+
+    /// Creates a filter matching any of the named events, e.g. `["Transfer", "Approval"]`,
+    /// with the event signatures as alternatives for topic0. Logs fetched with it can be
+    /// decoded with `decode_log`, which picks the matching event per log.
+    #[wasm_bindgen]
+    pub fn create_multi_event_filter(&self, event_names: JsValue) -> Result<ContractEventFilter, JsValue> {
+        if !js_sys::Array::is_array(&event_names) {
+            return Err(JsValue::from_str("create_multi_event_filter expects an array of event names"));
+        }
+        
+        let mut filter: Option<ContractEventFilter> = None;
+        for name in js_sys::Array::from(&event_names).iter() {
+            let name = name.as_string().ok_or_else(|| JsValue::from_str("Event names must be strings"))?;
+            let event = self.events.get(&name)
+                .ok_or_else(|| JsValue::from_str(&format!("Event '{}' not found in ABI", name)))?;
+            if event.anonymous {
+                return Err(JsValue::from_str(&format!("Anonymous event '{}' has no signature topic to filter by", name)));
+            }
+            
+            let signature = compute_event_signature(&name, &event.inputs);
+            match filter.as_mut() {
+                Some(filter) => filter.add_event_signature(signature)?,
+                None => filter = Some(ContractEventFilter::new(signature, self.address.clone())),
+            }
+        }
+        
+        let mut filter = filter.ok_or_else(|| JsValue::from_str("At least one event name is required"))?;
+        filter.set_provider(self.provider.clone());
+        Ok(filter)
+    }
+
+    /// Waits for the next `event_name` log whose indexed parameters match `indexed_params`
+    /// (as in `create_event_filter`), polling until one appears or `timeout_ms` elapses.
+    /// Logs emitted before the call are never returned. Resolves with the log decoded as by `decode_log`.
+    #[wasm_bindgen]
+    pub async fn wait_for_event(&self, event_name: &str, indexed_params: JsValue, timeout_ms: u32) -> Result<JsValue, JsValue> {
+        let filter = self.create_event_filter(event_name, indexed_params, None)?;
+        let log = filter.wait_for_next(timeout_ms).await?;
+        self.decode_log(log)
+    }
+}
+
+impl Contract {
+    /// A copy of this contract at another address, reusing the parsed ABI
+    pub(crate) fn at_address(&self, address: &str) -> Result<Contract, JsValue> {
+        Ok(Contract {
+            address: normalize_address(address)?,
+            abi: self.abi.clone(),
+            functions: self.functions.clone(),
+            selectors: self.selectors.clone(),
+            events: self.events.clone(),
+            errors: self.errors.clone(),
+            check_code: self.check_code,
+            provider: self.provider.clone(),
+        })
+    }
+
+    /// Reads an address stored in the low 20 bytes of an EIP-1967 proxy slot
+    async fn read_proxy_address(&self, slot: &str, slot_name: &str) -> Result<String, JsValue> {
+        self.read_proxy_slot(slot).await?.ok_or_else(|| JsValue::from_str(&format!(
+            "Contract {} is not an EIP-1967 proxy: the {} slot is empty", self.address, slot_name
+        )))
+    }
+
+    /// Reads the address in an EIP-1967 proxy slot, or None when the slot is empty
+    async fn read_proxy_slot(&self, slot: &str) -> Result<Option<String>, JsValue> {
+        let word = self.get_storage_at(slot, JsValue::UNDEFINED).await?;
+        let bytes = hex_to_bytes(&word).map_err(|e| JsValue::from_str(&e))?;
+        if bytes.len() > 32 {
+            return Err(JsValue::from_str(&format!("Invalid storage word: {}", word)));
+        }
+        
+        // Providers may return the word without leading zeros
+        let mut padded = vec![0u8; 32 - bytes.len()];
+        padded.extend_from_slice(&bytes);
+        if padded.iter().all(|b| *b == 0) {
+            return Ok(None);
+        }
+        
+        Ok(Some(checksum_address(&padded[12..])))
+    }
+
+    /// Checks that the function is read-only, encodes the call and executes it with eth_call,
+    /// returning the function and the raw hex return data
+    async fn call_function(&self, function_name: &str, args: JsValue, block_tag: JsValue) -> Result<(&Function, String), JsValue> {
         // Check if function exists and is read-only
         let function = match self.functions.get(function_name) {
             Some(f) => {
@@ -212,57 +1010,66 @@ impl Contract {
             None => return Err(JsValue::from_str(&format!("Function '{}' not found in ABI", function_name))),
         };
 
+        self.ensure_deployed().await?;
+
         // Encode the function call
         let encoded_call = self.encode_function_call(function_name, args)?;
 
-        // Perform the call using Web3
         #[cfg(target_arch = "wasm32")]
         {
-            let window = web_sys::window().ok_or_else(|| JsValue::from_str("Could not access window"))?;
-            
-            if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-                return Err(JsValue::from_str("Ethereum provider not found"));
-            }
-
-            let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
-            
-            let request_fn = js_sys::Reflect::get(&ethereum, &JsValue::from_str("request"))?;
-            let request_fn = js_sys::Function::from(request_fn);
-            
-            let params = js_sys::Object::new();
-            js_sys::Reflect::set(&params, &JsValue::from_str("to"), &JsValue::from_str(&self.address))?;
-            js_sys::Reflect::set(&params, &JsValue::from_str("data"), &JsValue::from_str(&encoded_call))?;
+            let block_tag = normalize_block_tag(&block_tag)?;
+            let result = self.eth_call(&encoded_call, &block_tag).await?;
             
-            let args = js_sys::Object::new();
-            js_sys::Reflect::set(&args, &JsValue::from_str("method"), &JsValue::from_str("eth_call"))?;
-            
-            let params_array = js_sys::Array::new();
-            params_array.push(&params);
-            params_array.push(&JsValue::from_str("latest"));
-            
-            js_sys::Reflect::set(&args, &JsValue::from_str("params"), &params_array)?;
-            
-            let promise = request_fn.call1(&ethereum, &args)?;
-            let promise = js_sys::Promise::from(promise);
-            let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
-            
-            // Parse the result based on the function's output types
-            return decode_function_result(function, result);
+            Ok((function, result))
         }
 
         #[cfg(not(target_arch = "wasm32"))]
         {
+            let _ = (encoded_call, block_tag);
             // Mock response for testing
-            let mock_result = JsValue::from_str("0x0000000000000000000000000000000000000000000000000000000000000020");
-            return decode_function_result(function, mock_result);
+            Ok((function, "0x0000000000000000000000000000000000000000000000000000000000000020".to_string()))
         }
     }
 
-    /// Sends a transaction to execute a state-changing (nonpayable/payable) function on the contract
-    #[wasm_bindgen]
-    pub async fn send_transaction(&self, function_name: &str, args: JsValue, options: JsValue) -> Result<String, JsValue> {
+    /// When the code check is enabled, errors if no contract code exists at the address
+    async fn ensure_deployed(&self) -> Result<(), JsValue> {
+        if !self.check_code {
+            return Ok(());
+        }
+        
+        let code = self.request("eth_getCode", js_sys::Array::of2(&JsValue::from_str(&self.address), &JsValue::from_str("latest"))).await?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Invalid eth_getCode result"))?;
+        if !code.trim_start_matches("0x").chars().any(|c| c != '0') {
+            return Err(JsValue::from_str(&format!("No contract deployed at {} on the current network", self.address)));
+        }
+        Ok(())
+    }
+
+    /// The provider this contract was created with, or the default one
+    fn provider(&self) -> Result<JsValue, JsValue> {
+        match &self.provider {
+            Some(provider) => Ok(provider.clone()),
+            None => crate::eth_integration::get_provider(),
+        }
+    }
+
+    /// Sends a JSON-RPC request through the contract's provider, decoding reverts
+    /// with the contract's custom errors
+    async fn request(&self, method: &str, params: js_sys::Array) -> Result<JsValue, JsValue> {
+        crate::eth_integration::request_with_provider(&self.provider()?, method, params, &|data| self.custom_error_reason(data)).await
+    }
+
+    /// Executes an eth_call against the contract through its provider
+    async fn eth_call(&self, data: &str, block_tag: &str) -> Result<String, JsValue> {
+        crate::eth_integration::eth_call_with_provider(&self.provider()?, &self.address, data, block_tag, &|data| self.custom_error_reason(data)).await
+    }
+
+    /// Validates a state-changing call and builds its transaction object, checking that
+    /// value is only sent to payable functions and defaulting `from` to the connected account
+    async fn prepare_transaction(&self, function_name: &str, args: JsValue, options: &JsValue) -> Result<js_sys::Object, JsValue> {
         // Check if function exists and can modify state
-        let _function = match self.functions.get(function_name) {
+        let function = match self.functions.get(function_name) {
             Some(f) => {
                 if f.state_mutability == StateMutability::View || f.state_mutability == StateMutability::Pure {
                     return Err(JsValue::from_str(
@@ -274,24 +1081,35 @@ impl Contract {
             None => return Err(JsValue::from_str(&format!("Function '{}' not found in ABI", function_name))),
         };
 
+        self.ensure_deployed().await?;
+
         // Encode the function call
         let encoded_call = self.encode_function_call(function_name, args)?;
 
         // Prepare transaction options
-        let tx_options = js_sys::Object::from(options);
-        js_sys::Reflect::set(&tx_options, &JsValue::from_str("to"), &JsValue::from_str(&self.address))?;
-        js_sys::Reflect::set(&tx_options, &JsValue::from_str("data"), &JsValue::from_str(&encoded_call))?;
+        let tx_options = self.build_tx_object(&encoded_call, options)?;
+        
+        // Sending value to a non-payable function would revert
+        let value = js_sys::Reflect::get(&tx_options, &JsValue::from_str("value"))?.as_string().unwrap_or_default();
+        if !value.trim_start_matches("0x").trim_start_matches('0').is_empty() && function.state_mutability != StateMutability::Payable {
+            return Err(JsValue::from_str(&format!("Function '{}' is not payable and cannot receive value", function_name)));
+        }
+        
+        // Default the sender to the first connected account
+        if js_sys::Reflect::get(&tx_options, &JsValue::from_str("from"))?.is_undefined() {
+            let from = crate::eth_integration::require_account_with_provider(&self.provider()?).await?;
+            js_sys::Reflect::set(&tx_options, &JsValue::from_str("from"), &JsValue::from_str(&from))?;
+        }
+
+        Ok(tx_options)
+    }
 
-        // Send the transaction
+    /// Submits a prepared transaction object with eth_sendTransaction and returns its hash,
+    /// giving up after `timeout_ms` (the module default when None)
+    async fn submit_transaction(&self, tx_options: js_sys::Object, timeout_ms: Option<u32>) -> Result<String, JsValue> {
         #[cfg(target_arch = "wasm32")]
         {
-            let window = web_sys::window().ok_or_else(|| JsValue::from_str("Could not access window"))?;
-            
-            if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-                return Err(JsValue::from_str("Ethereum provider not found"));
-            }
-
-            let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
+            let ethereum = self.provider()?;
             
             let request_fn = js_sys::Reflect::get(&ethereum, &JsValue::from_str("request"))?;
             let request_fn = js_sys::Function::from(request_fn);
@@ -306,7 +1124,9 @@ impl Contract {
             
             let promise = request_fn.call1(&ethereum, &args)?;
             let promise = js_sys::Promise::from(promise);
-            let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+            let timeout_ms = timeout_ms.unwrap_or_else(crate::eth_integration::request_timeout);
+            let result = crate::eth_integration::with_timeout(promise, timeout_ms).await?
+                .map_err(|e| JsValue::from(RustWasmError::from_provider_error_with(&e, &|data| self.custom_error_reason(data))))?;
             
             // Return the transaction hash
             if let Some(tx_hash) = result.as_string() {
@@ -319,117 +1139,641 @@ impl Contract {
         #[cfg(not(target_arch = "wasm32"))]
         {
             // Mock transaction hash for testing
+            let _ = (tx_options, timeout_ms);
             Ok("0x".to_string() + &"1234567890abcdef".repeat(4))
         }
     }
 
-    /// Creates a new event subscription for the specified event
-    #[wasm_bindgen]
-    pub fn create_event_filter(&self, event_name: &str, indexed_params: JsValue) -> Result<ContractEventFilter, JsValue> {
-        // Check if event exists
-        let event = match self.events.get(event_name) {
-            Some(e) => e,
-            None => return Err(JsValue::from_str(&format!("Event '{}' not found in ABI", event_name))),
-        };
-
-        // Create the event signature
-        let event_signature = compute_event_signature(event_name, &event.inputs);
+    /// Builds a transaction object from the caller's options with `to` and `data` set.
+    /// Options are validated: `from` must be a valid address, numeric fields may be given
+    /// as numbers or decimal/hex strings and are normalized to `0x` quantities, and unknown
+    /// keys are dropped with a warning. The caller's options object is not modified.
+    fn build_tx_object(&self, encoded_call: &str, options: &JsValue) -> Result<js_sys::Object, JsValue> {
+        let tx_object = js_sys::Object::new();
         
-        // Create a new event filter
-        let mut filter = ContractEventFilter::new(event_signature, self.address.clone());
-        
-        // Handle indexed parameters if provided
-        if !indexed_params.is_null() && !indexed_params.is_undefined() {
-            let params_obj = js_sys::Object::from(indexed_params);
-            
-            // Add topics for indexed parameters
-            for (_i, param) in event.inputs.iter().enumerate() {
-                if param.indexed {
-                    let param_name = &param.name;
-                    if js_sys::Reflect::has(&params_obj, &JsValue::from_str(param_name)).unwrap_or(false) {
-                        let value = js_sys::Reflect::get(&params_obj, &JsValue::from_str(param_name))?;
-                        if let Some(value_str) = value.as_string() {
-                            filter.add_topic(value_str)?;
+        if options.is_object() {
+            let options_obj = js_sys::Object::from(options.clone());
+            for key in js_sys::Object::keys(&options_obj).iter() {
+                let key = key.as_string().unwrap_or_default();
+                let value = js_sys::Reflect::get(&options_obj, &JsValue::from_str(&key))?;
+                if value.is_undefined() || value.is_null() {
+                    continue;
+                }
+                
+                match key.as_str() {
+                    "from" => {
+                        let from = normalize_address(&value.as_string().unwrap_or_default())?;
+                        js_sys::Reflect::set(&tx_object, &JsValue::from_str("from"), &JsValue::from_str(&from))?;
+                    },
+                    // `gasLimit` is accepted as an alias of `gas`
+                    "value" | "gas" | "gasLimit" | "gasPrice" | "maxFeePerGas" | "maxPriorityFeePerGas" | "nonce" | "type" | "chainId" => {
+                        let quantity = normalize_quantity(&value)
+                            .map_err(|e| JsValue::from_str(&format!("Invalid '{}' option: {}", key, e)))?;
+                        let key = if key == "gasLimit" { "gas" } else { key.as_str() };
+                        js_sys::Reflect::set(&tx_object, &JsValue::from_str(key), &JsValue::from_str(&quantity))?;
+                    },
+                    "accessList" => {
+                        // An EIP-2930 access list must be an array of { address, storageKeys } entries
+                        if !js_sys::Array::is_array(&value) {
+                            return Err(JsValue::from_str("accessList must be an array of { address, storageKeys } entries"));
                         }
-                    }
+                        js_sys::Reflect::set(&tx_object, &JsValue::from_str("accessList"), &value)?;
+                    },
+                    // Not part of the transaction: read by `timeout_option` when it is sent
+                    "timeout" => {},
+                    "to" | "data" => {
+                        crate::utils::log_warning(&format!("Ignoring '{}' option: it is set from the contract and function call", key));
+                    },
+                    _ => {
+                        crate::utils::log_warning(&format!("Ignoring unknown transaction option '{}'", key));
+                    },
                 }
             }
         }
         
-        Ok(filter)
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("to"), &JsValue::from_str(&self.address))?;
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("data"), &JsValue::from_str(encoded_call))?;
+        
+        Ok(tx_object)
+    }
+
+    /// Decodes the logs of a receipt that were emitted by this contract with `decode_log`.
+    /// Logs from other addresses, or of events not in the ABI, are skipped.
+    fn decode_own_logs(&self, receipt: &JsValue) -> Result<js_sys::Array, JsValue> {
+        let events = js_sys::Array::new();
+        for log in self.own_logs(receipt)? {
+            if let Ok(event) = self.decode_log(log) {
+                events.push(&event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Returns the logs of a receipt whose address is this contract
+    fn own_logs(&self, receipt: &JsValue) -> Result<Vec<JsValue>, JsValue> {
+        let logs = js_sys::Reflect::get(receipt, &JsValue::from_str("logs"))?;
+        if !js_sys::Array::is_array(&logs) {
+            return Ok(Vec::new());
+        }
+        
+        let mut own_logs = Vec::new();
+        for log in js_sys::Array::from(&logs).iter() {
+            let address = js_sys::Reflect::get(&log, &JsValue::from_str("address"))?
+                .as_string()
+                .unwrap_or_default();
+            if addresses_equal(&address, &self.address) {
+                own_logs.push(log);
+            }
+        }
+        Ok(own_logs)
+    }
+
+    /// Finds the event a log belongs to by its topic0 and decodes its parameter values.
+    /// Indexed parameters are read in turn from `topics[1..]` and non-indexed ones from the
+    /// data tuple, so values are returned in declaration order.
+    fn decode_event_log(&self, log: &EventLog) -> Result<(&Event, Vec<LogValue>), String> {
+        let mut topics = Vec::with_capacity(log.topics.len());
+        for topic in &log.topics {
+            topics.push(hex_to_bytes(topic)?);
+        }
+        let data = hex_to_bytes(&log.data)?;
+        
+        let topic0 = log.topics.first()
+            .map(|topic| topic.to_lowercase())
+            .ok_or_else(|| "Log has no event signature topic".to_string())?;
+        let event = self.events.values()
+            .find(|e| !e.anonymous && compute_event_signature(&e.name, &e.inputs) == topic0)
+            .ok_or_else(|| format!("No event found for topic '{}'", topic0))?;
+        
+        let mut types = Vec::with_capacity(event.inputs.len());
+        for input in &event.inputs {
+            let param_type = ParamType::from_event_parameter(input)
+                .ok_or_else(|| format!("Unsupported parameter type '{}'", input.r#type))?;
+            types.push(param_type);
+        }
+        
+        // Non-indexed parameters are ABI-encoded together as one tuple in the data
+        let data_types: Vec<ParamType> = event.inputs.iter().zip(types.iter())
+            .filter(|(input, _)| !input.indexed)
+            .map(|(_, param_type)| param_type.clone())
+            .collect();
+        let data_tokens = decode(&data_types, &data)
+            .map_err(|e| format!("Failed to decode data of '{}': {}", event.name, e))?;
+        
+        // Walk the inputs with one cursor over the topics (after topic0) and one over the data values
+        let mut topic_cursor = topics.into_iter().skip(1);
+        let mut data_cursor = data_tokens.into_iter();
+        let mut values = Vec::with_capacity(event.inputs.len());
+        for (input, param_type) in event.inputs.iter().zip(types.iter()) {
+            let value = if input.indexed {
+                let topic = topic_cursor.next()
+                    .ok_or_else(|| format!("Log is missing the topic for indexed parameter '{}'", input.name))?;
+                // Indexed dynamic values, arrays and structs are stored as the hash of their encoding
+                if param_type.is_dynamic() || matches!(param_type, ParamType::Tuple(_) | ParamType::FixedArray(..)) {
+                    LogValue::Hashed(topic)
+                } else {
+                    let token = decode(std::slice::from_ref(param_type), &topic)
+                        .map_err(|e| format!("Failed to decode topic for '{}': {}", input.name, e))?
+                        .remove(0);
+                    LogValue::Decoded(token)
+                }
+            } else {
+                let token = data_cursor.next()
+                    .ok_or_else(|| "Log data has fewer values than the event".to_string())?;
+                LogValue::Decoded(token)
+            };
+            values.push(value);
+        }
+        
+        Ok((event, values))
+    }
+
+    /// Finds the function whose selector matches the given one
+    fn find_function_by_selector(&self, selector: &str) -> Option<&Function> {
+        let selector = selector.to_lowercase();
+        let selector = if selector.starts_with("0x") { selector } else { format!("0x{}", selector) };
+        
+        self.functions.values()
+            .find(|f| self.selector_of(f) == selector)
+    }
+
+    /// Returns a function's selector from the cache, computing it for functions missing there
+    fn selector_of(&self, function: &Function) -> String {
+        self.selectors.get(&function.name)
+            .cloned()
+            .unwrap_or_else(|| compute_function_selector(&function.name, &function.inputs))
+    }
+
+    /// Matches revert data against the ABI's custom errors and decodes the arguments
+    fn decode_custom_error(&self, data: &[u8]) -> Option<(&CustomError, Vec<Token>)> {
+        if data.len() < 4 {
+            return None;
+        }
+        
+        let selector = bytes_to_hex(&data[..4]);
+        let error = self.errors.values()
+            .find(|e| compute_function_selector(&e.name, &e.inputs) == selector)?;
+        let types = error.inputs.iter()
+            .map(ParamType::from_parameter)
+            .collect::<Option<Vec<_>>>()?;
+        let tokens = decode(&types, &data[4..]).ok()?;
+        Some((error, tokens))
+    }
+
+    /// Formats a custom error revert as `Name(arg: value, ...)`
+    fn custom_error_reason(&self, data: &[u8]) -> Option<String> {
+        let (error, tokens) = self.decode_custom_error(data)?;
+        let args: Vec<String> = error.inputs.iter().zip(tokens.iter())
+            .map(|(input, token)| {
+                if input.name.is_empty() {
+                    token.to_decimal_string()
+                } else {
+                    format!("{}: {}", input.name, token.to_decimal_string())
+                }
+            })
+            .collect();
+        Some(format!("{}({})", error.name, args.join(", ")))
     }
 }
 
-// Helper functions for Contract implementation
+/// A decoded event parameter value
+enum LogValue {
+    Decoded(Token),
+    /// Indexed value that is only available as the topic hash
+    Hashed(Vec<u8>),
+}
 
-/// Computes a function selector from the function name and input parameters
-fn compute_function_selector(name: &str, inputs: &[Parameter]) -> String {
-    // In a real implementation, we would compute the Keccak256 hash of the function signature
-    // and take the first 4 bytes. For this demo, we'll use a simplified approach.
-    let mut signature = name.to_string();
-    signature.push('(');
+/// Reads a log object as returned by the provider (e.g. from a receipt or eth_getLogs)
+fn event_log_from_js(log: &JsValue) -> Result<EventLog, JsValue> {
+    let get_string = |key: &str| -> Result<Option<String>, JsValue> {
+        Ok(js_sys::Reflect::get(log, &JsValue::from_str(key))?.as_string())
+    };
+    let get_quantity = |key: &str| -> Result<Option<u64>, JsValue> {
+        Ok(get_string(key)?.and_then(|value| u64::from_str_radix(value.trim_start_matches("0x"), 16).ok()))
+    };
+    
+    let topics_value = js_sys::Reflect::get(log, &JsValue::from_str("topics"))?;
+    if !js_sys::Array::is_array(&topics_value) {
+        return Err(JsValue::from_str("Log has no topics array"));
+    }
+    let mut topics = Vec::new();
+    for topic in js_sys::Array::from(&topics_value).iter() {
+        topics.push(topic.as_string().ok_or_else(|| JsValue::from_str("Log topics must be hex strings"))?);
+    }
     
-    for (i, input) in inputs.iter().enumerate() {
-        if i > 0 {
-            signature.push(',');
+    Ok(EventLog {
+        address: get_string("address")?.unwrap_or_default(),
+        topics,
+        data: get_string("data")?.unwrap_or_else(|| "0x".to_string()),
+        block_number: get_quantity("blockNumber")?,
+        transaction_hash: get_string("transactionHash")?,
+        transaction_index: get_quantity("transactionIndex")?,
+        block_hash: get_string("blockHash")?,
+        log_index: get_quantity("logIndex")?,
+        removed: js_sys::Reflect::get(log, &JsValue::from_str("removed"))?.as_bool(),
+    })
+}
+
+// Helper functions for Contract implementation
+
+/// Returns the canonical type string of a parameter, expanding tuples into
+/// their component types, e.g. `(address,uint256)[]` for a `tuple[]`
+fn canonical_type(r#type: &str, components: Option<&[Parameter]>) -> String {
+    match r#type.strip_prefix("tuple") {
+        Some(suffix) => {
+            let component_types: Vec<String> = components.unwrap_or_default().iter()
+                .map(|c| canonical_type(&c.r#type, c.components.as_deref()))
+                .collect();
+            format!("({}){}", component_types.join(","), suffix)
+        },
+        None => r#type.to_string(),
+    }
+}
+
+/// EIP-1967 storage slot holding a proxy's implementation address
+const EIP1967_IMPLEMENTATION_SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+/// EIP-1967 storage slot holding a proxy's admin address
+const EIP1967_ADMIN_SLOT: &str = "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+
+/// Version of the `Contract::to_snapshot` format, bumped when its layout changes
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// A parsed contract as serialized by `Contract::to_snapshot`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContractSnapshot {
+    version: u32,
+    address: String,
+    abi: String,
+    functions: HashMap<String, Function>,
+    selectors: HashMap<String, String>,
+    events: HashMap<String, Event>,
+    errors: HashMap<String, CustomError>,
+    check_code: bool,
+}
+
+/// Function metadata returned by `Contract::functions`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FunctionInfo<'a> {
+    name: &'a str,
+    signature: String,
+    selector: String,
+    inputs: &'a [Parameter],
+    outputs: &'a [Parameter],
+    state_mutability: &'a StateMutability,
+}
+
+/// Event metadata returned by `Contract::events`; anonymous events have no topic0
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventInfo<'a> {
+    name: &'a str,
+    signature: String,
+    topic0: Option<String>,
+    inputs: &'a [EventParameter],
+    anonymous: bool,
+}
+
+/// A validation failure reported by `validate_args`; errors not tied to a single
+/// argument (such as a wrong argument count) have no index
+#[derive(Serialize)]
+struct ArgumentError {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    r#type: String,
+    message: String,
+}
+
+impl ArgumentError {
+    fn general(message: String) -> ArgumentError {
+        ArgumentError {
+            index: None,
+            name: String::new(),
+            r#type: String::new(),
+            message,
         }
-        signature.push_str(&input.r#type);
+    }
+}
+
+/// Renders a parameter as a JSON ABI entry, including tuple components
+fn parameter_json(param: &Parameter) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "name": param.name,
+        "type": param.r#type,
+    });
+    if let Some(components) = &param.components {
+        json["components"] = components.iter().map(parameter_json).collect();
+    }
+    json
+}
+
+/// The per-call request timeout in milliseconds from a `timeout` transaction option, if given
+fn timeout_option(options: &JsValue) -> Result<Option<u32>, JsValue> {
+    if !options.is_object() {
+        return Ok(None);
     }
     
-    signature.push(')');
+    let timeout = js_sys::Reflect::get(options, &JsValue::from_str("timeout"))?;
+    if timeout.is_undefined() || timeout.is_null() {
+        return Ok(None);
+    }
+    match timeout.as_f64() {
+        Some(ms) if ms >= 0.0 && ms.fract() == 0.0 && ms <= u32::MAX as f64 => Ok(Some(ms as u32)),
+        _ => Err(JsValue::from_str("Invalid 'timeout' option: expected a whole number of milliseconds")),
+    }
+}
+
+/// Normalizes a numeric transaction field given as a JS number or a decimal/hex string
+/// to the `0x` hex quantity the JSON-RPC API expects
+fn normalize_quantity(value: &JsValue) -> Result<String, String> {
+    let (negative, magnitude) = if let Some(number) = value.as_f64() {
+        if !number.is_finite() || number.fract() != 0.0 || number.abs() > 9_007_199_254_740_991.0 {
+            return Err(format!("{} is not a safe integer, pass it as a string", number));
+        }
+        (number < 0.0, primitive_types::U256::from(number.abs() as u64))
+    } else if let Some(text) = value.as_string() {
+        parse_integer(&text)?
+    } else {
+        return Err("expected a number or a decimal/hex string".to_string());
+    };
     
-    // Use a simple hash function for demo purposes
-    let hash = signature.bytes().fold(0u32, |acc, b| acc.wrapping_add(b as u32));
-    format!("0x{:08x}", hash)
+    if negative {
+        return Err("must not be negative".to_string());
+    }
+    Ok(format!("0x{:x}", magnitude))
 }
 
-/// Computes an event signature (topic0) from the event name and input parameters
-fn compute_event_signature(name: &str, inputs: &[EventParameter]) -> String {
-    // In a real implementation, we would compute the Keccak256 hash of the event signature
-    // For this demo, we'll use a simplified approach.
-    let mut signature = name.to_string();
-    signature.push('(');
+/// Converts tuple arguments given as JS objects (e.g. `{ maker, taker, amount }`) into the
+/// positional arrays `tokenize` expects, ordering the values by the tuple's component names.
+/// Nested tuples and arrays of tuples are converted recursively; positional arrays are kept,
+/// and values of other types are returned unchanged.
+fn positional_tuple_value(param: &Parameter, value: &JsValue) -> Result<JsValue, String> {
+    let suffix = match param.r#type.strip_prefix("tuple") {
+        Some(suffix) => suffix,
+        None => return Ok(value.clone()),
+    };
     
-    for (i, input) in inputs.iter().enumerate() {
-        if i > 0 {
-            signature.push(',');
+    // Arrays of tuples: convert each element with the array dimension removed
+    if let Some(open) = suffix.rfind('[') {
+        if !js_sys::Array::is_array(value) {
+            return Ok(value.clone());
         }
-        signature.push_str(&input.r#type);
+        let element = Parameter {
+            name: param.name.clone(),
+            r#type: format!("tuple{}", &suffix[..open]),
+            components: param.components.clone(),
+        };
+        return js_sys::Array::from(value).iter()
+            .map(|item| positional_tuple_value(&element, &item))
+            .collect::<Result<js_sys::Array, String>>()
+            .map(JsValue::from);
+    }
+    
+    let components = param.components.as_deref().unwrap_or_default();
+    if js_sys::Array::is_array(value) {
+        let items = js_sys::Array::from(value);
+        if items.length() as usize != components.len() {
+            // Left to tokenize, which reports the length mismatch
+            return Ok(value.clone());
+        }
+        return components.iter().zip(items.iter())
+            .map(|(component, item)| positional_tuple_value(component, &item))
+            .collect::<Result<js_sys::Array, String>>()
+            .map(JsValue::from);
+    }
+    if !value.is_object() {
+        return Ok(value.clone());
     }
     
-    signature.push(')');
+    let values = tuple_field_values(components, |component| {
+        let field = js_sys::Reflect::get(value, &JsValue::from_str(&component.name))
+            .map_err(|_| format!("Failed to read tuple field '{}'", component.name))?;
+        if field.is_undefined() {
+            return Ok(None);
+        }
+        positional_tuple_value(component, &field).map(Some)
+    })?;
     
-    // Use a simple hash function for demo purposes
-    let hash = signature.bytes().fold(0u32, |acc, b| acc.wrapping_add(b as u32));
-    format!("0x{:064x}", hash)
+    Ok(values.into_iter().collect::<js_sys::Array>().into())
 }
 
-/// Decodes a function's result based on its output types
-fn decode_function_result(function: &Function, result: JsValue) -> Result<JsValue, JsValue> {
-    // In a real implementation, we would use proper ABI decoding
-    // For this demo, we'll return the raw result
+/// Collects the fields of a tuple passed by name in component order, using `field` to look
+/// up each component's value (`None` when absent). Unnamed components and missing fields are errors.
+fn tuple_field_values<T>(
+    components: &[Parameter],
+    mut field: impl FnMut(&Parameter) -> Result<Option<T>, String>,
+) -> Result<Vec<T>, String> {
+    components.iter().enumerate().map(|(index, component)| {
+        if component.name.is_empty() {
+            return Err(format!("Tuple component {} has no name, so the tuple must be passed as an array", index));
+        }
+        field(component)?.ok_or_else(|| format!("Missing tuple field '{}'", component.name))
+    }).collect()
+}
+
+/// Computes the selectors of all functions, keyed by function name
+fn compute_selectors(functions: &HashMap<String, Function>) -> HashMap<String, String> {
+    functions.values()
+        .map(|function| (function.name.clone(), compute_function_selector(&function.name, &function.inputs)))
+        .collect()
+}
+
+/// Computes a function selector from the function name and input parameters
+fn compute_function_selector(name: &str, inputs: &[Parameter]) -> String {
+    let input_types: Vec<String> = inputs.iter()
+        .map(|input| canonical_type(&input.r#type, input.components.as_deref()))
+        .collect();
+    let signature = get_function_signature(name, &input_types);
+    
+    // The selector is the first 4 bytes of the Keccak256 hash of the signature
+    let hash = keccak256(signature.as_bytes());
+    bytes_to_hex(&hash[..4])
+}
+
+/// Computes an event signature (topic0) from the event name and input parameters
+fn compute_event_signature(name: &str, inputs: &[EventParameter]) -> String {
+    let input_types: Vec<String> = inputs.iter()
+        .map(|input| canonical_type(&input.r#type, input.components.as_deref()))
+        .collect();
+    let signature = get_event_signature(name, &input_types);
     
+    // topic0 is the full Keccak256 hash of the signature
+    bytes_to_hex(&keccak256(signature.as_bytes()))
+}
+
+/// Decodes a function's result based on its output types.
+/// Integers are returned as decimal strings, `string` as a JS string, `bytes` and
+/// `bytesN` as 0x-prefixed hex. Multiple outputs are returned as a JS array.
+fn decode_function_result(function: &Function, result: JsValue) -> Result<JsValue, JsValue> {
     // If the function has no outputs, return null
     if function.outputs.is_empty() {
         return Ok(JsValue::null());
     }
     
+    let result_str = result.as_string()
+        .ok_or_else(|| JsValue::from_str("Call result is not a hex string"))?;
+    let data = hex_to_bytes(&result_str).map_err(|e| JsValue::from_str(&e))?;
+    let tokens = decode_function_output(function, &data).map_err(|e| JsValue::from_str(&e))?;
+    
     // For functions with a single output, return the decoded value
-    if function.outputs.len() == 1 {
-        if let Some(result_str) = result.as_string() {
-            // For numeric types, try to convert from hex
-            if function.outputs[0].r#type.starts_with("uint") || 
-               function.outputs[0].r#type.starts_with("int") {
-                if let Ok(decimal) = hex_to_decimal(&result_str) {
-                    return Ok(JsValue::from_str(&decimal));
-                }
-            }
-            // For other types, return the raw value
-            return Ok(result);
-        }
+    if tokens.len() == 1 {
+        return Ok(tokens[0].to_js_value());
     }
     
-    // For functions with multiple outputs, return a JS object
-    // This would require more sophisticated decoding in a real implementation
-    Ok(result)
-} 
\ No newline at end of file
+    // For functions with multiple outputs, return a JS array
+    Ok(Token::Tuple(tokens).to_js_value())
+}
+
+/// Decodes ABI-encoded return data into one token per function output
+fn decode_function_output(function: &Function, data: &[u8]) -> Result<Vec<Token>, String> {
+    let mut types = Vec::new();
+    for output in &function.outputs {
+        let param_type = ParamType::from_parameter(output)
+            .ok_or_else(|| format!("Unsupported output type '{}'", output.r#type))?;
+        types.push(param_type);
+    }
+    
+    decode(&types, data).map_err(|e| format!("Failed to decode result of '{}': {}", function.name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitive_types::U256;
+
+    const TEST_ADDRESS: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+    fn test_contract(abi: &str) -> Contract {
+        Contract::new(TEST_ADDRESS.to_string(), abi.to_string()).unwrap()
+    }
+
+    #[test]
+    fn decodes_string_bytes_and_fixed_bytes_results() {
+        let contract = test_contract(r#"[{"type":"function","name":"info","inputs":[],"outputs":[
+            {"name":"label","type":"string"},{"name":"payload","type":"bytes"},{"name":"tag","type":"bytes4"}
+        ],"stateMutability":"view"}]"#);
+        let data = hex_to_bytes(concat!(
+            "0x",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "deadbeef00000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000005",
+            "68656c6c6f000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "1234000000000000000000000000000000000000000000000000000000000000",
+        )).unwrap();
+
+        let tokens = decode_function_output(&contract.functions["info"], &data).unwrap();
+        assert_eq!(tokens, vec![
+            Token::String("hello".to_string()),
+            Token::Bytes(vec![0x12, 0x34]),
+            Token::FixedBytes(vec![0xde, 0xad, 0xbe, 0xef]),
+        ]);
+
+        assert!(decode_function_output(&contract.functions["info"], &data[..96]).is_err());
+    }
+
+    #[test]
+    fn encodes_dynamic_and_fixed_array_arguments() {
+        let contract = test_contract(r#"[{"type":"function","name":"batch","inputs":[
+            {"name":"amounts","type":"uint256[]"},{"name":"recipients","type":"address[3]"}
+        ],"outputs":[],"stateMutability":"nonpayable"}]"#);
+        let function = &contract.functions["batch"];
+        let types: Vec<ParamType> = function.inputs.iter()
+            .map(|input| ParamType::from_parameter(input).unwrap())
+            .collect();
+        assert!(types == vec![
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::FixedArray(Box::new(ParamType::Address), 3),
+        ]);
+
+        let recipients = [
+            "0x1111111111111111111111111111111111111111",
+            "0x2222222222222222222222222222222222222222",
+            "0x3333333333333333333333333333333333333333",
+        ];
+        let tokens = vec![
+            Token::Array(vec![Token::Uint(U256::from(1)), Token::Uint(U256::from(2))]),
+            Token::FixedArray(recipients.iter().map(|a| Token::Address(a.to_string())).collect()),
+        ];
+        let calldata = format!("{}{}", compute_function_selector(&function.name, &function.inputs), &bytes_to_hex(&encode(&tokens).unwrap())[2..]);
+
+        let selector = bytes_to_hex(&ethers::utils::id("batch(uint256[],address[3])"));
+        assert_eq!(calldata, format!("{}{}", selector, concat!(
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "0000000000000000000000001111111111111111111111111111111111111111",
+            "0000000000000000000000002222222222222222222222222222222222222222",
+            "0000000000000000000000003333333333333333333333333333333333333333",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        )));
+    }
+
+    #[test]
+    fn decodes_uint256_array_event_data() {
+        let contract = test_contract(r#"[{"type":"event","name":"Batch","anonymous":false,"inputs":[
+            {"name":"operator","type":"address","indexed":true},
+            {"name":"tags","type":"uint256[]","indexed":true},
+            {"name":"ids","type":"uint256[]","indexed":false}
+        ]}]"#);
+        let operator = format!("0x{:0>64}", "1111111111111111111111111111111111111111");
+        let tags_hash = bytes_to_hex(&[0xab; 32]);
+        let data = ethers::abi::encode(&[ethers::abi::Token::Array(vec![
+            ethers::abi::Token::Uint(1.into()),
+            ethers::abi::Token::Uint(2.into()),
+            ethers::abi::Token::Uint(ethers::types::U256::MAX),
+        ])]);
+        let log = EventLog {
+            address: TEST_ADDRESS.to_string(),
+            topics: vec![
+                bytes_to_hex(&ethers::utils::keccak256("Batch(address,uint256[],uint256[])")),
+                operator,
+                tags_hash,
+            ],
+            data: bytes_to_hex(&data),
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            block_hash: None,
+            log_index: None,
+            removed: None,
+        };
+
+        let (event, values) = contract.decode_event_log(&log).unwrap();
+        assert_eq!(event.name, "Batch");
+        assert!(matches!(&values[0], LogValue::Decoded(Token::Address(address))
+            if address == "0x1111111111111111111111111111111111111111"));
+        assert!(matches!(&values[1], LogValue::Hashed(hash) if hash == &[0xab; 32]));
+        match &values[2] {
+            LogValue::Decoded(token) => assert_eq!(token, &Token::Array(vec![
+                Token::Uint(U256::from(1)),
+                Token::Uint(U256::from(2)),
+                Token::Uint(U256::MAX),
+            ])),
+            LogValue::Hashed(_) => panic!("ids should be decoded from the log data"),
+        }
+    }
+
+    #[test]
+    fn tuple_fields_follow_component_order() {
+        let components: Vec<Parameter> = serde_json::from_str(r#"[
+            {"name":"maker","type":"address"},{"name":"taker","type":"address"},{"name":"amount","type":"uint256"}
+        ]"#).unwrap();
+        let fields: HashMap<&str, &str> = [("amount", "100"), ("maker", "0x01"), ("taker", "0x02")].into_iter().collect();
+        let lookup = |component: &Parameter| Ok(fields.get(component.name.as_str()).copied());
+        assert_eq!(tuple_field_values(&components, lookup).unwrap(), vec!["0x01", "0x02", "100"]);
+
+        let partial: HashMap<&str, &str> = [("maker", "0x01"), ("amount", "100")].into_iter().collect();
+        let error = tuple_field_values(&components, |component| Ok(partial.get(component.name.as_str()).copied())).unwrap_err();
+        assert_eq!(error, "Missing tuple field 'taker'");
+
+        let unnamed: Vec<Parameter> = serde_json::from_str(r#"[{"name":"","type":"uint256"}]"#).unwrap();
+        assert!(tuple_field_values(&unnamed, |_| Ok(Some("1"))).is_err());
+    }
+}
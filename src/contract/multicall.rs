@@ -0,0 +1,42 @@
+use wasm_bindgen::prelude::*;
+use crate::eth_integration::eth_call;
+use super::codec::{decode, encode_function_data, Token};
+use super::events::ParamType;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+
+/// Batches several read-only calls into a single eth_call using Multicall3's `aggregate3`,
+/// with failures allowed. Multicall3 is deployed at `0xcA11bde05977b3631167028862bE2a173976CA11`
+/// on most chains.
+/// Returns the return data of each call in order, or `None` for calls that reverted.
+pub async fn aggregate3(multicall_address: &str, calls: &[(String, Vec<u8>)]) -> Result<Vec<Option<Vec<u8>>>, JsValue> {
+    let call_tokens = calls.iter()
+        .map(|(target, data)| Token::Tuple(vec![
+            Token::Address(target.clone()),
+            Token::Bool(true),
+            Token::Bytes(data.clone()),
+        ]))
+        .collect();
+
+    let calldata = encode_function_data("aggregate3((address,bool,bytes)[])", &[Token::Array(call_tokens)])
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    let result = eth_call(multicall_address, &bytes_to_hex(&calldata), "latest").await?;
+    let result_bytes = hex_to_bytes(&result).map_err(|e| JsValue::from_str(&e))?;
+
+    // aggregate3 returns (bool success, bytes returnData)[]
+    let result_type = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let tokens = decode(&[result_type], &result_bytes).map_err(|e| JsValue::from_str(&e))?;
+
+    match tokens.into_iter().next() {
+        Some(Token::Array(results)) if results.len() == calls.len() => {
+            Ok(results.into_iter().map(|result| match result {
+                Token::Tuple(fields) => match fields.as_slice() {
+                    [Token::Bool(true), Token::Bytes(data)] => Some(data.clone()),
+                    _ => None,
+                },
+                _ => None,
+            }).collect())
+        },
+        _ => Err(JsValue::from_str("Unexpected multicall result")),
+    }
+}
@@ -0,0 +1,190 @@
+use wasm_bindgen::prelude::*;
+use super::abi::parse_type_string;
+use super::codec::{decode, encode, tokenize, Token};
+use super::events::ParamType;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+
+/// Encodes values with Solidity's non-standard packed mode (`abi.encodePacked`).
+/// Values are concatenated without padding or length prefixes: `address` takes 20 bytes,
+/// `uintN`/`intN` take N/8 bytes, `bool` one byte, `bytesN` N bytes and `string`/`bytes`
+/// their raw contents. Array elements are padded to 32 bytes, as Solidity does.
+/// Returns the concatenated `0x`-prefixed hex, e.g. for reproducing `keccak256(abi.encodePacked(...))`.
+#[wasm_bindgen]
+pub fn encode_packed(types: JsValue, values: JsValue) -> Result<String, JsValue> {
+    if !js_sys::Array::is_array(&types) || !js_sys::Array::is_array(&values) {
+        return Err(JsValue::from_str("encode_packed expects an array of types and an array of values"));
+    }
+
+    let types = js_sys::Array::from(&types).to_vec();
+    let values = js_sys::Array::from(&values).to_vec();
+    if types.len() != values.len() {
+        return Err(JsValue::from_str(&format!(
+            "Expected {} values for {} types, got {}", types.len(), types.len(), values.len()
+        )));
+    }
+
+    let mut packed = Vec::new();
+    for (type_value, value) in types.iter().zip(values.iter()) {
+        let type_str = type_value.as_string()
+            .ok_or_else(|| JsValue::from_str("Types must be strings such as 'uint256' or 'address'"))?;
+        let param_type = ParamType::from_str(&type_str)
+            .ok_or_else(|| JsValue::from_str(&format!("Unsupported type '{}'", type_str)))?;
+
+        let token = tokenize(&param_type, value)
+            .map_err(|e| JsValue::from_str(&format!("Invalid value for {}: {}", type_str, e)))?;
+        packed.extend(pack_token(&param_type, &token, false).map_err(|e| JsValue::from_str(&e))?);
+    }
+
+    Ok(bytes_to_hex(&packed))
+}
+
+/// Encodes values with the standard ABI encoding, like ethers' `defaultAbiCoder.encode`:
+/// static values are padded to 32-byte words and dynamic ones referenced by offset.
+/// Types are strings such as `"uint256"`, `"string[]"` or `"(address,bytes)"`; tuple values
+/// are arrays with one entry per component. Returns `0x`-prefixed hex.
+#[wasm_bindgen]
+pub fn encode_abi(types: JsValue, values: JsValue) -> Result<String, JsValue> {
+    let param_types = parse_types(&types, "encode_abi")?;
+    if !js_sys::Array::is_array(&values) {
+        return Err(JsValue::from_str("encode_abi expects an array of values"));
+    }
+    
+    let values = js_sys::Array::from(&values).to_vec();
+    if param_types.len() != values.len() {
+        return Err(JsValue::from_str(&format!(
+            "Expected {} values, got {}", param_types.len(), values.len()
+        )));
+    }
+    
+    let mut tokens = Vec::with_capacity(values.len());
+    for (index, (param_type, value)) in param_types.iter().zip(values.iter()).enumerate() {
+        let token = tokenize(param_type, value)
+            .map_err(|e| JsValue::from_str(&format!("Invalid value at index {} for {}: {}", index, param_type, e)))?;
+        tokens.push(token);
+    }
+    
+    let encoded = encode(&tokens).map_err(|e| JsValue::from_str(&e))?;
+    Ok(bytes_to_hex(&encoded))
+}
+
+/// Decodes ABI-encoded `data` (e.g. an eth_call result or log data) against an array of
+/// type strings such as `["address", "uint256[]", "(bool,string)"]`.
+/// Returns a JS array with one value per type, converted as contract call results are:
+/// integers as decimal strings, bytes as `0x` hex, arrays and tuples as nested arrays.
+#[wasm_bindgen]
+pub fn decode_abi(types: JsValue, data: &str) -> Result<JsValue, JsValue> {
+    let types = parse_types(&types, "decode_abi")?;
+    let data = hex_to_bytes(data).map_err(|e| JsValue::from_str(&format!("Invalid data: {}", e)))?;
+    
+    let tokens = decode(&types, &data).map_err(|e| JsValue::from_str(&format!("Failed to decode data: {}", e)))?;
+    Ok(Token::Tuple(tokens).to_js_value())
+}
+
+/// Parses a JS array of type strings, naming the calling function in errors
+fn parse_types(types: &JsValue, function_name: &str) -> Result<Vec<ParamType>, JsValue> {
+    if !js_sys::Array::is_array(types) {
+        return Err(JsValue::from_str(&format!("{} expects an array of type strings", function_name)));
+    }
+    
+    js_sys::Array::from(types).iter()
+        .map(|type_value| {
+            let type_str = type_value.as_string()
+                .ok_or_else(|| JsValue::from_str("Types must be strings such as 'uint256' or 'address'"))?;
+            parse_type_string(&type_str).map_err(|e| JsValue::from_str(&e))
+        })
+        .collect()
+}
+
+/// Packs a single token. Inside arrays every element is padded to a full 32-byte word.
+fn pack_token(param_type: &ParamType, token: &Token, in_array: bool) -> Result<Vec<u8>, String> {
+    match (param_type, token) {
+        (ParamType::Address, Token::Address(address)) => {
+            let bytes = hex_to_bytes(address)?;
+            Ok(if in_array { left_pad(&bytes) } else { bytes })
+        },
+        (ParamType::Uint(bits), Token::Uint(value)) | (ParamType::Int(bits), Token::Int(value)) => {
+            let mut word = [0u8; 32];
+            value.to_big_endian(&mut word);
+            // Two's complement values truncate correctly to their low-order bytes
+            let size = if in_array { 32 } else { bits / 8 };
+            Ok(word[32 - size..].to_vec())
+        },
+        (ParamType::Bool, Token::Bool(value)) => {
+            let byte = vec![u8::from(*value)];
+            Ok(if in_array { left_pad(&byte) } else { byte })
+        },
+        (ParamType::FixedBytes(_), Token::FixedBytes(bytes)) => {
+            let mut bytes = bytes.clone();
+            if in_array {
+                bytes.resize(32, 0);
+            }
+            Ok(bytes)
+        },
+        (ParamType::String, Token::String(value)) if !in_array => Ok(value.as_bytes().to_vec()),
+        (ParamType::Bytes, Token::Bytes(bytes)) if !in_array => Ok(bytes.clone()),
+        (ParamType::Array(element_type), Token::Array(tokens))
+        | (ParamType::FixedArray(element_type, _), Token::FixedArray(tokens)) if !in_array => {
+            let mut packed = Vec::new();
+            for token in tokens {
+                packed.extend(pack_token(element_type, token, true)?);
+            }
+            Ok(packed)
+        },
+        _ => Err(format!("Type {} is not supported in packed encoding", param_type)),
+    }
+}
+
+fn left_pad(bytes: &[u8]) -> Vec<u8> {
+    let mut word = vec![0u8; 32 - bytes.len()];
+    word.extend_from_slice(bytes);
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::Token as EthersToken;
+    use primitive_types::U256;
+
+    #[test]
+    fn encode_abi_matches_abi_encode_for_mixed_tuples() {
+        let types: Vec<ParamType> = ["uint256", "(uint256,string,address,bytes)", "bool"].iter()
+            .map(|type_str| parse_type_string(type_str).unwrap())
+            .collect();
+        let address = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let tokens = vec![
+            Token::Uint(U256::from(7)),
+            Token::Tuple(vec![
+                Token::Uint(U256::from(1_000_000)),
+                Token::String("hello world".to_string()),
+                Token::Address(address.to_string()),
+                Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef, 0x00]),
+            ]),
+            Token::Bool(true),
+        ];
+
+        let expected = ethers::abi::encode(&[
+            EthersToken::Uint(7.into()),
+            EthersToken::Tuple(vec![
+                EthersToken::Uint(1_000_000.into()),
+                EthersToken::String("hello world".to_string()),
+                EthersToken::Address(address.parse().unwrap()),
+                EthersToken::Bytes(vec![0xde, 0xad, 0xbe, 0xef, 0x00]),
+            ]),
+            EthersToken::Bool(true),
+        ]);
+        let encoded = encode(&tokens).unwrap();
+        assert_eq!(bytes_to_hex(&encoded), bytes_to_hex(&expected));
+
+        let decoded = decode(&types, &encoded).unwrap();
+        assert_eq!(decoded[0], tokens[0]);
+        assert_eq!(decoded[2], tokens[2]);
+        match &decoded[1] {
+            Token::Tuple(components) => {
+                assert_eq!(components[1], Token::String("hello world".to_string()));
+                assert_eq!(components[3], Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef, 0x00]));
+            },
+            other => panic!("expected a tuple, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use wasm_bindgen::prelude::*;
+use primitive_types::U256;
+use super::codec::{encode, parse_integer, tokenize, Token};
+use super::events::ParamType;
+use super::utils::{bytes_to_hex, keccak256};
+
+/// Computes the storage slot of a mapping entry: `keccak256(pad(key) ++ pad(base_slot))`.
+/// `base_slot` is the mapping's own slot (decimal or hex) and `key_type` the Solidity key type.
+/// Value-type keys are padded to 32 bytes; `string` and `bytes` keys are hashed unpadded,
+/// as Solidity does. Nested mappings are resolved by passing the result back as `base_slot`.
+#[wasm_bindgen]
+pub fn mapping_slot(base_slot: &str, key: JsValue, key_type: &str) -> Result<String, JsValue> {
+    let base_slot = parse_slot(base_slot).map_err(|e| JsValue::from_str(&e))?;
+    let param_type = ParamType::from_str(key_type)
+        .ok_or_else(|| JsValue::from_str(&format!("Unsupported key type '{}'", key_type)))?;
+    
+    let token = tokenize(&param_type, &key)
+        .map_err(|e| JsValue::from_str(&format!("Invalid {} key: {}", key_type, e)))?;
+    let mut preimage = match token {
+        Token::String(value) => value.into_bytes(),
+        Token::Bytes(bytes) => bytes,
+        Token::Array(_) | Token::FixedArray(_) | Token::Tuple(_) => {
+            return Err(JsValue::from_str(&format!("Type {} cannot be a mapping key", key_type)));
+        },
+        token => encode(&[token]).map_err(|e| JsValue::from_str(&e))?,
+    };
+    
+    let mut slot_word = [0u8; 32];
+    base_slot.to_big_endian(&mut slot_word);
+    preimage.extend_from_slice(&slot_word);
+    
+    Ok(bytes_to_hex(&keccak256(&preimage)))
+}
+
+/// Parses a storage slot given as a decimal or `0x` hex string
+pub fn parse_slot(slot: &str) -> Result<U256, String> {
+    match parse_integer(slot)? {
+        (false, slot) => Ok(slot),
+        (true, _) => Err(format!("Storage slot cannot be negative: {}", slot)),
+    }
+}
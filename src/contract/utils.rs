@@ -1,14 +1,77 @@
 use wasm_bindgen::prelude::*;
+use tiny_keccak::{Hasher, Keccak};
+use primitive_types::U256;
 
 /// Utility functions for Ethereum contract operations
 
+/// Compute the Keccak-256 hash of the given bytes
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Strip a `0x` or `0X` prefix, returning None when there is none
+pub fn strip_hex_prefix(hex: &str) -> Option<&str> {
+    hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X"))
+}
+
+/// Normalize hex (with or without a `0x`/`0X` prefix) to lowercase digits without the
+/// prefix, left-padded to an even length, so `0x0`, `0xA` and `0XABC` are all accepted
+pub fn normalize_hex_digits(hex: &str) -> Result<String, String> {
+    let digits = strip_hex_prefix(hex).unwrap_or(hex);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex string: {}", hex));
+    }
+    
+    let digits = digits.to_ascii_lowercase();
+    if digits.len() % 2 == 1 {
+        Ok(format!("0{}", digits))
+    } else {
+        Ok(digits)
+    }
+}
+
+/// Decode a hex string (with or without 0x prefix) into bytes.
+/// Unlike quantities, byte data must have an even number of digits.
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, String> {
+    let clean_hex = strip_hex_prefix(hex).unwrap_or(hex);
+    
+    if !clean_hex.is_ascii() {
+        return Err(format!("Invalid hex string: {}", hex));
+    }
+    
+    if !clean_hex.len().is_multiple_of(2) {
+        return Err(format!("Hex string has odd length: {}", hex));
+    }
+    
+    (0..clean_hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&clean_hex[i..i + 2], 16)
+                .map_err(|_| format!("Invalid hex string: {}", hex))
+        })
+        .collect()
+}
+
+/// Encode bytes as a 0x-prefixed lowercase hex string
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
+
 /// Convert a hex string to a decimal string
 pub fn hex_to_decimal(hex: &str) -> Result<String, String> {
-    // Remove 0x prefix if present
-    let clean_hex = hex.trim_start_matches("0x");
+    let clean_hex = normalize_hex_digits(hex)?;
     
     // Parse the hex string
-    match u128::from_str_radix(clean_hex, 16) {
+    match u128::from_str_radix(&clean_hex, 16) {
         Ok(value) => Ok(value.to_string()),
         Err(e) => Err(format!("Failed to parse hex value: {}", e)),
     }
@@ -45,26 +108,145 @@ pub fn pad_hex(hex: &str, length: usize) -> String {
     format!("0x{}", padded)
 }
 
-/// Convert a value to Wei (smallest Ethereum unit)
-pub fn to_wei(value: f64, unit: &str) -> Result<String, String> {
-    let multiplier = match unit.to_lowercase().as_str() {
-        "wei" => 1.0,
-        "kwei" | "babbage" | "femtoether" => 1_000.0,
-        "mwei" | "lovelace" | "picoether" => 1_000_000.0,
-        "gwei" | "shannon" | "nanoether" | "nano" => 1_000_000_000.0,
-        "microether" | "micro" => 1_000_000_000_000.0,
-        "milliether" | "milli" => 1_000_000_000_000_000.0,
-        "ether" | "eth" => 1_000_000_000_000_000_000.0,
+/// Left-pad `0x` hex with zero bytes to `bytes` bytes (default 32, one ABI word),
+/// as for numbers and addresses. An odd number of digits is padded to a whole byte first.
+#[wasm_bindgen]
+pub fn pad_left(hex: &str, bytes: Option<u32>) -> Result<String, JsValue> {
+    let data = normalize_hex_digits(hex)
+        .and_then(|digits| hex_to_bytes(&digits))
+        .map_err(|e| JsValue::from_str(&e))?;
+    
+    let size = checked_pad_size(&data, bytes, hex)?;
+    let mut padded = vec![0u8; size - data.len()];
+    padded.extend_from_slice(&data);
+    Ok(bytes_to_hex(&padded))
+}
+
+/// Right-pad `0x` hex with zero bytes to `bytes` bytes (default 32, one ABI word),
+/// as for `bytesN` values and dynamic data
+#[wasm_bindgen]
+pub fn pad_right(hex: &str, bytes: Option<u32>) -> Result<String, JsValue> {
+    let mut data = hex_to_bytes(hex).map_err(|e| JsValue::from_str(&e))?;
+    
+    let size = checked_pad_size(&data, bytes, hex)?;
+    data.resize(size, 0);
+    Ok(bytes_to_hex(&data))
+}
+
+/// Remove leading zero bytes from `0x` hex, e.g. `0x0000ab12` becomes `0xab12`;
+/// all-zero input becomes `0x`
+#[wasm_bindgen]
+pub fn strip_zeros(hex: &str) -> Result<String, JsValue> {
+    let data = hex_to_bytes(hex).map_err(|e| JsValue::from_str(&e))?;
+    let first_non_zero = data.iter().position(|b| *b != 0).unwrap_or(data.len());
+    Ok(bytes_to_hex(&data[first_non_zero..]))
+}
+
+/// The padded size in bytes, erroring when the data is already longer
+fn checked_pad_size(data: &[u8], bytes: Option<u32>, hex: &str) -> Result<usize, JsValue> {
+    let size = bytes.unwrap_or(32) as usize;
+    if data.len() > size {
+        return Err(JsValue::from_str(&format!("{} is longer than {} bytes", hex, size)));
+    }
+    Ok(size)
+}
+
+/// Encode a short string as `bytes32`, as used by contracts that predate the `string` type:
+/// the UTF-8 bytes right-padded with zeros. At most 31 bytes, so the value stays null-terminated.
+#[wasm_bindgen]
+pub fn string_to_bytes32(s: &str) -> Result<String, JsValue> {
+    let data = s.as_bytes();
+    if data.len() > 31 {
+        return Err(JsValue::from_str(&format!(
+            "String is {} bytes long; bytes32 strings hold at most 31 bytes", data.len()
+        )));
+    }
+    
+    let mut padded = data.to_vec();
+    padded.resize(32, 0);
+    Ok(bytes_to_hex(&padded))
+}
+
+/// Decode a `bytes32` string, dropping the trailing zero padding
+#[wasm_bindgen]
+pub fn bytes32_to_string(b: &str) -> Result<String, JsValue> {
+    let data = hex_to_bytes(b).map_err(|e| JsValue::from_str(&e))?;
+    if data.len() != 32 {
+        return Err(JsValue::from_str(&format!("Expected 32 bytes, got {}", data.len())));
+    }
+    
+    let end = data.iter().rposition(|byte| *byte != 0).map_or(0, |i| i + 1);
+    String::from_utf8(data[..end].to_vec())
+        .map_err(|_| JsValue::from_str(&format!("{} is not a valid UTF-8 string", b)))
+}
+
+/// Runtime code of an EIP-1167 minimal proxy before and after the 20-byte implementation address
+const MINIMAL_PROXY_PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+const MINIMAL_PROXY_SUFFIX: [u8; 15] = [0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3];
+
+/// If `code_hex` is the runtime code of an EIP-1167 minimal proxy (clone), returns the
+/// checksummed implementation address embedded in it
+#[wasm_bindgen]
+pub fn is_minimal_proxy(code_hex: &str) -> Option<String> {
+    let code = hex_to_bytes(code_hex).ok()?;
+    let address = code.strip_prefix(&MINIMAL_PROXY_PREFIX[..])?
+        .strip_suffix(&MINIMAL_PROXY_SUFFIX[..])?;
+    
+    (address.len() == 20).then(|| crate::utils::checksum_address(address))
+}
+
+/// ERC-165 interface IDs of common standards, for use with `Contract::supports_interface`
+pub const INTERFACE_ID_ERC165: &str = "0x01ffc9a7";
+pub const INTERFACE_ID_ERC721: &str = "0x80ac58cd";
+pub const INTERFACE_ID_ERC721_METADATA: &str = "0x5b5e139f";
+pub const INTERFACE_ID_ERC721_ENUMERABLE: &str = "0x780e9d63";
+pub const INTERFACE_ID_ERC1155: &str = "0xd9b67a26";
+pub const INTERFACE_ID_ERC1155_METADATA_URI: &str = "0x0e89341c";
+pub const INTERFACE_ID_ERC2981: &str = "0x2a55205a";
+
+/// Convert a decimal amount in the given unit (e.g. `"123456789.123456789"` ether) to Wei,
+/// the smallest Ethereum unit. The string is shifted by the unit's decimals exactly, so all
+/// 18 fractional digits of an ether amount survive; digits finer than one wei are an error.
+#[wasm_bindgen]
+pub fn to_wei(value: &str, unit: &str) -> Result<String, JsValue> {
+    unit_to_wei(value, unit).map_err(|e| JsValue::from_str(&e))
+}
+
+fn unit_to_wei(value: &str, unit: &str) -> Result<String, String> {
+    let decimals = match unit.to_lowercase().as_str() {
+        "wei" => 0,
+        "kwei" | "babbage" | "femtoether" => 3,
+        "mwei" | "lovelace" | "picoether" => 6,
+        "gwei" | "shannon" | "nanoether" | "nano" => 9,
+        "microether" | "micro" => 12,
+        "milliether" | "milli" => 15,
+        "ether" | "eth" => 18,
         _ => return Err(format!("Unknown unit: {}", unit)),
     };
     
-    // Calculate wei value
-    let wei = value * multiplier;
+    let value = value.trim();
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(format!("Invalid amount: '{}'", value));
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid amount '{}': only digits and one decimal point are allowed", value));
+    }
     
-    // Round to integer
-    let wei_int = wei.round() as u128;
+    // Trailing zeros beyond the unit's precision are harmless; other digits would be lost
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals {
+        return Err(format!(
+            "Amount '{}' has more than {} decimal places, which is below 1 wei",
+            value, decimals
+        ));
+    }
     
-    Ok(wei_int.to_string())
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    let wei = U256::from_dec_str(&digits)
+        .map_err(|_| format!("Amount '{}' {} does not fit in 256 bits", value, unit))?;
+    
+    Ok(wei.to_string())
 }
 
 /// Format a number with commas for thousands
@@ -79,4 +261,40 @@ pub fn format_with_commas(value: &str) -> String {
     }
     
     chars.into_iter().collect()
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::format_units;
+
+    #[test]
+    fn quantities_accept_odd_length_and_uppercase_prefix() {
+        assert_eq!(normalize_hex_digits("0x0").unwrap(), "00");
+        assert_eq!(normalize_hex_digits("0xA").unwrap(), "0a");
+        assert_eq!(normalize_hex_digits("0XABC").unwrap(), "0abc");
+        assert_eq!(hex_to_decimal("0x0").unwrap(), "0");
+        assert_eq!(hex_to_decimal("0xA").unwrap(), "10");
+        assert_eq!(hex_to_decimal("0XABC").unwrap(), "2748");
+    }
+
+    #[test]
+    fn byte_data_rejects_odd_length() {
+        assert!(hex_to_bytes("0xabc").is_err());
+        assert!(hex_to_bytes("0x0").is_err());
+        assert_eq!(hex_to_bytes("0XABCD").unwrap(), vec![0xab, 0xcd]);
+        assert_eq!(hex_to_bytes("0x").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn to_wei_round_trips_full_ether_precision() {
+        let wei = unit_to_wei("123456789.123456789123456789", "ether").unwrap();
+        assert_eq!(wei, "123456789123456789123456789");
+        assert_eq!(format_units(&wei, 18).unwrap(), "123456789.123456789123456789");
+
+        assert_eq!(unit_to_wei("1.5", "gwei").unwrap(), "1500000000");
+        assert_eq!(unit_to_wei("7.00", "wei").unwrap(), "7");
+        assert!(unit_to_wei("0.0000000000000000001", "ether").is_err());
+        assert!(unit_to_wei("1", "finney").is_err());
+    }
+}
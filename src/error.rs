@@ -0,0 +1,124 @@
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+use std::fmt;
+use crate::eth_integration::errors::{self, describe_error_code};
+
+/// Errors returned to JavaScript as `{ code, message }` objects, so callers can tell
+/// failure kinds apart (e.g. a rejected request from a revert) without parsing messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RustWasmError {
+    InvalidAddress(String),
+    ProviderNotInstalled,
+    NotConnected,
+    UserRejected,
+    ChainNotAdded,
+    InsufficientFunds(String),
+    Reverted(String),
+    AbiParse(String),
+    RpcError { code: i64, message: String },
+    /// The provider did not answer within the request timeout, in milliseconds
+    Timeout(u32),
+}
+
+/// The JS shape of an error; `rpcCode` carries the provider's numeric code for RPC errors
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorObject<'a> {
+    code: &'a str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_code: Option<i64>,
+}
+
+impl RustWasmError {
+    /// Stable identifier exposed to JS as the `code` field
+    pub fn code(&self) -> &'static str {
+        match self {
+            RustWasmError::InvalidAddress(_) => "INVALID_ADDRESS",
+            RustWasmError::ProviderNotInstalled => "PROVIDER_NOT_INSTALLED",
+            RustWasmError::NotConnected => "NOT_CONNECTED",
+            RustWasmError::UserRejected => "USER_REJECTED",
+            RustWasmError::ChainNotAdded => "CHAIN_NOT_ADDED",
+            RustWasmError::InsufficientFunds(_) => "INSUFFICIENT_FUNDS",
+            RustWasmError::Reverted(_) => "REVERTED",
+            RustWasmError::AbiParse(_) => "ABI_PARSE",
+            RustWasmError::RpcError { .. } => "RPC_ERROR",
+            RustWasmError::Timeout(_) => "TIMEOUT",
+        }
+    }
+
+    /// Maps an error thrown by an EIP-1193 provider to the matching variant.
+    /// Revert data in `error.data` (or `error.data.data`) is decoded into the revert reason.
+    pub fn from_provider_error(error: &JsValue) -> RustWasmError {
+        Self::from_provider_error_with(error, &|_| None)
+    }
+
+    /// Like `from_provider_error`, but revert data is first offered to `decode_revert`
+    /// (e.g. to match a contract's custom errors) before the `Error(string)`/`Panic(uint256)` fallback
+    pub(crate) fn from_provider_error_with(error: &JsValue, decode_revert: &dyn Fn(&[u8]) -> Option<String>) -> RustWasmError {
+        let field = |target: &JsValue, key: &str| js_sys::Reflect::get(target, &JsValue::from_str(key)).ok();
+
+        let code = field(error, "code").and_then(|c| c.as_f64()).map(|c| c as i64);
+        let message = error.as_string()
+            .or_else(|| field(error, "message")?.as_string())
+            .unwrap_or_else(|| "Unknown provider error".to_string());
+
+        let data = field(error, "data");
+        let revert_data = data.as_ref().and_then(|data| {
+            data.as_string().or_else(|| field(data, "data")?.as_string())
+        });
+        if let Some(revert_data) = revert_data {
+            let reason = crate::contract::hex_to_bytes(&revert_data).ok()
+                .and_then(|bytes| decode_revert(&bytes).or_else(|| crate::contract::decode_revert_reason(&bytes)));
+            if let Some(reason) = reason {
+                return RustWasmError::Reverted(reason);
+            }
+        }
+
+        match code {
+            Some(errors::USER_REJECTED) => RustWasmError::UserRejected,
+            Some(errors::CHAIN_NOT_ADDED) => RustWasmError::ChainNotAdded,
+            Some(errors::INVALID_INPUT) if message.to_lowercase().contains("insufficient funds") => {
+                RustWasmError::InsufficientFunds(message)
+            },
+            _ if message.to_lowercase().contains("execution reverted") => RustWasmError::Reverted(message),
+            // Errors without a code are reported as internal JSON-RPC errors
+            code => RustWasmError::RpcError { code: code.unwrap_or(errors::INTERNAL_ERROR), message },
+        }
+    }
+}
+
+impl fmt::Display for RustWasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RustWasmError::InvalidAddress(address) => write!(f, "Invalid Ethereum address: {}", address),
+            RustWasmError::ProviderNotInstalled => write!(f, "No Ethereum provider found: install a wallet such as MetaMask"),
+            RustWasmError::NotConnected => write!(f, "Wallet is not connected: no accounts are available"),
+            RustWasmError::UserRejected => write!(f, "User rejected the request"),
+            RustWasmError::ChainNotAdded => write!(f, "The requested chain has not been added to the wallet"),
+            RustWasmError::InsufficientFunds(message) => write!(f, "Insufficient funds: {}", message),
+            RustWasmError::Reverted(reason) => write!(f, "Transaction reverted: {}", reason),
+            RustWasmError::AbiParse(message) => write!(f, "Failed to parse ABI: {}", message),
+            RustWasmError::RpcError { code, message } => {
+                write!(f, "RPC error {} ({}): {}", code, describe_error_code(*code), message)
+            },
+            RustWasmError::Timeout(ms) => write!(f, "request timed out after {}ms", ms),
+        }
+    }
+}
+
+impl From<RustWasmError> for JsValue {
+    fn from(error: RustWasmError) -> JsValue {
+        let rpc_code = match &error {
+            RustWasmError::RpcError { code, .. } => Some(*code),
+            _ => None,
+        };
+        let object = ErrorObject {
+            code: error.code(),
+            message: error.to_string(),
+            rpc_code,
+        };
+
+        serde_wasm_bindgen::to_value(&object).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    }
+}
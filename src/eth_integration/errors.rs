@@ -0,0 +1,75 @@
+use wasm_bindgen::prelude::*;
+
+// Error codes reported by EIP-1193 providers and Ethereum JSON-RPC nodes
+
+// EIP-1193 provider errors
+pub const USER_REJECTED: i64 = 4001;
+pub const UNAUTHORIZED: i64 = 4100;
+pub const UNSUPPORTED_METHOD: i64 = 4200;
+pub const DISCONNECTED: i64 = 4900;
+pub const CHAIN_DISCONNECTED: i64 = 4901;
+pub const CHAIN_NOT_ADDED: i64 = 4902;
+
+// Standard JSON-RPC 2.0 errors
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+// EIP-1474 server errors, within the -32000..-32099 range reserved for implementations
+pub const INVALID_INPUT: i64 = -32000;
+pub const RESOURCE_NOT_FOUND: i64 = -32001;
+pub const RESOURCE_UNAVAILABLE: i64 = -32002;
+pub const TRANSACTION_REJECTED: i64 = -32003;
+pub const METHOD_NOT_SUPPORTED: i64 = -32004;
+pub const LIMIT_EXCEEDED: i64 = -32005;
+pub const JSON_RPC_VERSION_NOT_SUPPORTED: i64 = -32006;
+
+/// Describes a provider or JSON-RPC error code in words
+pub fn describe_error_code(code: i64) -> &'static str {
+    match code {
+        USER_REJECTED => "User rejected the request",
+        UNAUTHORIZED => "The requested account or method is not authorized",
+        UNSUPPORTED_METHOD => "The provider does not support this method",
+        DISCONNECTED => "The provider is disconnected from all chains",
+        CHAIN_DISCONNECTED => "The provider is not connected to the requested chain",
+        CHAIN_NOT_ADDED => "The requested chain has not been added to the wallet",
+        PARSE_ERROR => "Invalid JSON",
+        INVALID_REQUEST => "Invalid JSON-RPC request",
+        METHOD_NOT_FOUND => "Method not found",
+        INVALID_PARAMS => "Invalid method parameters",
+        INTERNAL_ERROR => "Internal JSON-RPC error",
+        INVALID_INPUT => "Invalid input",
+        RESOURCE_NOT_FOUND => "Resource not found",
+        RESOURCE_UNAVAILABLE => "Resource unavailable",
+        TRANSACTION_REJECTED => "Transaction rejected",
+        METHOD_NOT_SUPPORTED => "Method not supported",
+        LIMIT_EXCEEDED => "Request limit exceeded",
+        JSON_RPC_VERSION_NOT_SUPPORTED => "JSON-RPC version not supported",
+        -32099..=-32007 => "Server error",
+        _ => "Unknown error",
+    }
+}
+
+/// Extracts `code` and `message` from an error thrown by a provider request and formats
+/// them as e.g. "User rejected the request (4001): User denied account access".
+/// Plain string errors are returned unchanged.
+pub fn provider_error_message(error: &JsValue) -> String {
+    if let Some(message) = error.as_string() {
+        return message;
+    }
+    
+    let code = js_sys::Reflect::get(error, &JsValue::from_str("code")).ok()
+        .and_then(|code| code.as_f64())
+        .map(|code| code as i64);
+    let message = js_sys::Reflect::get(error, &JsValue::from_str("message")).ok()
+        .and_then(|message| message.as_string());
+    
+    match (code, message) {
+        (Some(code), Some(message)) => format!("{} ({}): {}", describe_error_code(code), code, message),
+        (Some(code), None) => format!("{} ({})", describe_error_code(code), code),
+        (None, Some(message)) => message,
+        (None, None) => "Unknown provider error".to_string(),
+    }
+}
@@ -1,23 +1,53 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::TransactionData;
+use crate::error::RustWasmError;
 use wasm_bindgen::JsCast;
+use std::cell::{Cell, RefCell};
+use primitive_types::U256;
 
 #[cfg(target_arch = "wasm32")]
 use web_sys::{console, window};
 
+pub mod errors;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::NativeClient;
+
+thread_local! {
+    // Provider set with `set_provider`, used instead of `window.ethereum`
+    static CUSTOM_PROVIDER: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+    // Default provider request timeout in milliseconds, set with `set_request_timeout`; 0 waits forever
+    static REQUEST_TIMEOUT_MS: Cell<u32> = const { Cell::new(0) };
+    // How many times read requests are retried after a transient failure, set with `set_rpc_retries`
+    static RPC_RETRIES: Cell<u32> = const { Cell::new(DEFAULT_RPC_RETRIES) };
+}
+
+/// Retries of read requests after a transient failure when `set_rpc_retries` is not called
+pub const DEFAULT_RPC_RETRIES: u32 = 2;
+
+/// Delay before the first retry of a read request, doubled for each further attempt
+const RETRY_BASE_DELAY_MS: u32 = 250;
+
 /// Ethereum integration module for interacting with Ethereum networks.
 /// This module provides functions to interact with Ethereum providers,
 /// send transactions, and query blockchain data.
 
-/// Get the current Ethereum provider (MetaMask or other web3 provider)
+/// Get the current Ethereum provider: the one set with `set_provider`, otherwise the
+/// injected `window.ethereum` (MetaMask or other web3 provider)
 pub fn get_provider() -> Result<JsValue, JsValue> {
+    if let Some(provider) = CUSTOM_PROVIDER.with(|custom| custom.borrow().clone()) {
+        return Ok(provider);
+    }
+    
     #[cfg(target_arch = "wasm32")]
     {
-        let window = web_sys::window().ok_or_else(|| JsValue::from_str("Could not access window"))?;
+        let window = web_sys::window().ok_or(RustWasmError::ProviderNotInstalled)?;
         
         if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-            return Err(JsValue::from_str("Ethereum provider not found"));
+            return Err(RustWasmError::ProviderNotInstalled.into());
         }
 
         let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
@@ -31,43 +61,311 @@ pub fn get_provider() -> Result<JsValue, JsValue> {
     }
 }
 
-/// Get the connected accounts from the Ethereum provider
-pub async fn get_accounts() -> Result<Vec<String>, JsValue> {
-    let provider = get_provider()?;
+/// Use the given EIP-1193 provider (any object with a `request` method, e.g. from
+/// WalletConnect or the Coinbase Wallet SDK) for all requests instead of `window.ethereum`
+#[wasm_bindgen]
+pub fn set_provider(provider: JsValue) -> Result<(), JsValue> {
+    check_provider(&provider)?;
+    CUSTOM_PROVIDER.with(|custom| *custom.borrow_mut() = Some(provider));
+    Ok(())
+}
+
+/// Go back to using the injected `window.ethereum` provider
+#[wasm_bindgen]
+pub fn clear_provider() {
+    CUSTOM_PROVIDER.with(|custom| *custom.borrow_mut() = None);
+}
+
+/// Errors unless the value is an object with a `request` method
+pub(crate) fn check_provider(provider: &JsValue) -> Result<(), JsValue> {
+    let has_request = provider.is_object()
+        && js_sys::Reflect::get(provider, &JsValue::from_str("request"))?.is_function();
+    if !has_request {
+        return Err(JsValue::from_str("Provider must be an object with a request method (EIP-1193)"));
+    }
+    Ok(())
+}
+
+/// Send a JSON-RPC request with the given method and params through the Ethereum provider
+pub async fn request(method: &str, params: js_sys::Array) -> Result<JsValue, JsValue> {
+    request_with_provider(&get_provider()?, method, params, &|_| None).await
+}
+
+/// Send a JSON-RPC request through an explicit provider, decoding revert data with `decode_revert`
+pub(crate) async fn request_with_provider(
+    provider: &JsValue,
+    method: &str,
+    params: js_sys::Array,
+    decode_revert: &dyn Fn(&[u8]) -> Option<String>,
+) -> Result<JsValue, JsValue> {
+    let request_fn = js_sys::Reflect::get(provider, &JsValue::from_str("request"))?
+        .dyn_into::<js_sys::Function>()?;
     
-    #[cfg(target_arch = "wasm32")]
-    {
-        let request_fn = js_sys::Reflect::get(&provider, &JsValue::from_str("request"))?
-            .dyn_into::<js_sys::Function>()?;
-        
-        let args = js_sys::Object::new();
-        js_sys::Reflect::set(&args, &JsValue::from_str("method"), &JsValue::from_str("eth_accounts"))?;
+    let args = js_sys::Object::new();
+    js_sys::Reflect::set(&args, &JsValue::from_str("method"), &JsValue::from_str(method))?;
+    js_sys::Reflect::set(&args, &JsValue::from_str("params"), &params)?;
+    
+    let promise = request_fn.call1(provider, &args)?;
+    let promise = js_sys::Promise::from(promise);
+    with_timeout(promise, request_timeout()).await?
+        .map_err(|e| RustWasmError::from_provider_error_with(&e, decode_revert).into())
+}
+
+/// Set how many times read requests (contract calls, balances and logs) are retried, with
+/// exponential backoff, after a transient failure such as an internal error or rate limit.
+/// `0` disables retries. Transactions are never retried.
+#[wasm_bindgen]
+pub fn set_rpc_retries(retries: u32) {
+    RPC_RETRIES.with(|count| count.set(retries));
+}
+
+/// How many times read requests are retried after a transient failure
+#[wasm_bindgen]
+pub fn rpc_retries() -> u32 {
+    RPC_RETRIES.with(|count| count.get())
+}
+
+/// Send an idempotent read request, retrying transient failures up to `rpc_retries` times
+pub(crate) async fn read_request(method: &str, params: js_sys::Array) -> Result<JsValue, JsValue> {
+    read_request_with_provider(&get_provider()?, method, params, &|_| None).await
+}
+
+/// Like `request_with_provider`, but retries transient failures up to `rpc_retries` times with
+/// exponential backoff. Only for requests that are safe to repeat, never for sending transactions.
+pub(crate) async fn read_request_with_provider(
+    provider: &JsValue,
+    method: &str,
+    params: js_sys::Array,
+    decode_revert: &dyn Fn(&[u8]) -> Option<String>,
+) -> Result<JsValue, JsValue> {
+    let mut attempt = 0;
+    let mut delay_ms = RETRY_BASE_DELAY_MS;
+    loop {
+        match request_with_provider(provider, method, params.clone(), decode_revert).await {
+            Err(error) if attempt < rpc_retries() && is_transient_error(&error) => {
+                sleep_ms(delay_ms).await?;
+                attempt += 1;
+                delay_ms = delay_ms.saturating_mul(2);
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Returns true for request errors that a retry may fix: internal errors, unavailable
+/// resources and rate limits. User rejections, reverts and other errors are final.
+fn is_transient_error(error: &JsValue) -> bool {
+    let field = |key: &str| js_sys::Reflect::get(error, &JsValue::from_str(key)).ok();
+    
+    // Only plain RPC errors qualify; rejections and reverts have their own codes
+    if field("code").and_then(|code| code.as_string()).as_deref() != Some("RPC_ERROR") {
+        return false;
+    }
+    
+    let rpc_code = field("rpcCode").and_then(|code| code.as_f64()).map(|code| code as i64);
+    if matches!(rpc_code, Some(errors::INTERNAL_ERROR) | Some(errors::RESOURCE_UNAVAILABLE)) {
+        return true;
+    }
+    
+    let message = field("message").and_then(|message| message.as_string()).unwrap_or_default().to_lowercase();
+    ["rate limit", "too many requests"].iter().any(|pattern| message.contains(pattern))
+}
+
+/// Send several JSON-RPC requests as a single batch.
+/// Takes an array of `{ method, params }` objects and returns their results in request order.
+/// Providers that don't accept batches (most EIP-1193 wallets) are sent the requests one by one.
+#[wasm_bindgen]
+pub async fn rpc_batch(requests: JsValue) -> Result<JsValue, JsValue> {
+    if !js_sys::Array::is_array(&requests) {
+        return Err(JsValue::from_str("rpc_batch expects an array of { method, params } objects"));
+    }
+    
+    // Validate the requests and build the JSON-RPC payload with sequential ids
+    let mut calls = Vec::new();
+    let payload = js_sys::Array::new();
+    for (id, item) in js_sys::Array::from(&requests).iter().enumerate() {
+        let method = js_sys::Reflect::get(&item, &JsValue::from_str("method"))?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str(&format!("Request {} is missing a method", id)))?;
+        let params = js_sys::Reflect::get(&item, &JsValue::from_str("params"))?;
+        let params = if params.is_undefined() || params.is_null() {
+            js_sys::Array::new()
+        } else if js_sys::Array::is_array(&params) {
+            js_sys::Array::from(&params)
+        } else {
+            return Err(JsValue::from_str(&format!("Params of request {} ({}) must be an array", id, method)));
+        };
         
-        let promise = request_fn.call1(&provider, &args)?;
-        let promise = js_sys::Promise::from(promise);
-        let accounts = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        let entry = js_sys::Object::new();
+        js_sys::Reflect::set(&entry, &JsValue::from_str("jsonrpc"), &JsValue::from_str("2.0"))?;
+        js_sys::Reflect::set(&entry, &JsValue::from_str("id"), &JsValue::from_f64(id as f64))?;
+        js_sys::Reflect::set(&entry, &JsValue::from_str("method"), &JsValue::from_str(&method))?;
+        js_sys::Reflect::set(&entry, &JsValue::from_str("params"), &params)?;
+        payload.push(&entry);
+        calls.push((method, params));
+    }
+    
+    if calls.is_empty() {
+        return Ok(js_sys::Array::new().into());
+    }
+    
+    if let Some(results) = send_batch(&payload, calls.len()).await? {
+        return Ok(results.into());
+    }
+    
+    // Batches not supported: fall back to individual requests
+    let results = js_sys::Array::new();
+    for (method, params) in calls {
+        results.push(&request(&method, params).await?);
+    }
+    Ok(results.into())
+}
+
+/// Sends a batch payload through the provider's legacy `sendAsync`.
+/// Returns None when the provider has no batch support or rejects the batch,
+/// and an error if an individual request in the batch failed.
+async fn send_batch(payload: &js_sys::Array, count: usize) -> Result<Option<js_sys::Array>, JsValue> {
+    let provider = get_provider()?;
+    let send_async = js_sys::Reflect::get(&provider, &JsValue::from_str("sendAsync"))?;
+    if !send_async.is_function() {
+        return Ok(None);
+    }
+    
+    let send = js_sys::Function::new_with_args(
+        "provider, payload",
+        r#"
+        return new Promise((resolve, reject) => {
+            provider.sendAsync(payload, (error, response) => error ? reject(error) : resolve(response));
+        });
+        "#
+    );
+    let promise = js_sys::Promise::from(send.call2(&JsValue::null(), &provider, payload)?);
+    let responses = match wasm_bindgen_futures::JsFuture::from(promise).await {
+        Ok(responses) if js_sys::Array::is_array(&responses) => js_sys::Array::from(&responses),
+        _ => return Ok(None),
+    };
+    if responses.length() as usize != count {
+        return Ok(None);
+    }
+    
+    // Responses may arrive in any order, so place them by id
+    let results = js_sys::Array::new_with_length(count as u32);
+    for response in responses.iter() {
+        let id = js_sys::Reflect::get(&response, &JsValue::from_str("id"))?
+            .as_f64()
+            .map(|id| id as usize)
+            .filter(|id| *id < count)
+            .ok_or_else(|| JsValue::from_str("Batch response has an unknown id"))?;
         
-        if let Ok(accounts_array) = accounts.dyn_into::<js_sys::Array>() {
-            let mut result = Vec::new();
-            for i in 0..accounts_array.length() {
-                if let Some(account) = accounts_array.get(i).as_string() {
-                    result.push(account);
-                }
-            }
-            Ok(result)
-        } else {
-            Err(JsValue::from_str("Failed to parse accounts"))
+        let error = js_sys::Reflect::get(&response, &JsValue::from_str("error"))?;
+        if !error.is_undefined() && !error.is_null() {
+            return Err(JsValue::from_str(&format!(
+                "Batch request {} failed: {}", id, errors::provider_error_message(&error)
+            )));
         }
+        
+        results.set(id as u32, js_sys::Reflect::get(&response, &JsValue::from_str("result"))?);
     }
+    
+    Ok(Some(results))
+}
 
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        // For non-WASM environments, return mock accounts for testing
-        Ok(vec!["0x0000000000000000000000000000000000000000".to_string()])
+/// Execute an eth_call against the given address and return the raw hex result
+pub async fn eth_call(to: &str, data: &str, block_tag: &str) -> Result<String, JsValue> {
+    eth_call_with_provider(&get_provider()?, to, data, block_tag, &|_| None).await
+}
+
+/// Like `eth_call`, but sent through an explicit provider and with a revert first decoded with `decode_revert`
+pub(crate) async fn eth_call_with_provider(
+    provider: &JsValue,
+    to: &str,
+    data: &str,
+    block_tag: &str,
+    decode_revert: &dyn Fn(&[u8]) -> Option<String>,
+) -> Result<String, JsValue> {
+    let call_obj = js_sys::Object::new();
+    js_sys::Reflect::set(&call_obj, &JsValue::from_str("to"), &JsValue::from_str(to))?;
+    js_sys::Reflect::set(&call_obj, &JsValue::from_str("data"), &JsValue::from_str(data))?;
+    
+    let params = js_sys::Array::new();
+    params.push(&call_obj);
+    params.push(&JsValue::from_str(block_tag));
+    
+    let result = read_request_with_provider(provider, "eth_call", params, decode_revert).await?;
+    result.as_string().ok_or_else(|| JsValue::from_str("Invalid eth_call result"))
+}
+
+/// Get the balance of an address in wei, as a decimal string, at the given block (default "latest")
+#[wasm_bindgen]
+pub async fn get_balance(address: String, block_tag: JsValue) -> Result<String, JsValue> {
+    if !crate::utils::is_valid_eth_address(&address) {
+        return Err(RustWasmError::InvalidAddress(address).into());
+    }
+    
+    let block_tag = crate::utils::normalize_block_tag(&block_tag)?;
+    let balance = read_request("eth_getBalance", js_sys::Array::of2(&JsValue::from_str(&address), &JsValue::from_str(&block_tag))).await?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("Invalid eth_getBalance result"))?;
+    
+    crate::contract::normalize_hex_digits(&balance).ok()
+        .filter(|digits| !digits.is_empty())
+        .and_then(|digits| U256::from_str_radix(&digits, 16).ok())
+        .map(|balance| balance.to_string())
+        .ok_or_else(|| JsValue::from_str(&format!("Failed to parse balance: {}", balance)))
+}
+
+/// Get the number of transactions sent from an address (its nonce) at the given block (default "latest")
+#[wasm_bindgen]
+pub async fn get_transaction_count(address: String, block_tag: JsValue) -> Result<u64, JsValue> {
+    if !crate::utils::is_valid_eth_address(&address) {
+        return Err(RustWasmError::InvalidAddress(address).into());
     }
+    
+    let block_tag = crate::utils::normalize_block_tag(&block_tag)?;
+    let count = request("eth_getTransactionCount", js_sys::Array::of2(&JsValue::from_str(&address), &JsValue::from_str(&block_tag))).await?;
+    parse_hex_quantity(&count)
 }
 
-/// Connect to the Ethereum provider and request access to accounts
+/// Check whether an address holds contract code (as opposed to being an externally owned account)
+#[wasm_bindgen]
+pub async fn is_contract(address: String) -> Result<bool, JsValue> {
+    if !crate::utils::is_valid_eth_address(&address) {
+        return Err(RustWasmError::InvalidAddress(address).into());
+    }
+    
+    let params = js_sys::Array::new();
+    params.push(&JsValue::from_str(&address));
+    params.push(&JsValue::from_str("latest"));
+    
+    let code = request("eth_getCode", params).await?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("Invalid eth_getCode result"))?;
+    
+    Ok(code.trim_start_matches("0x").chars().any(|c| c != '0'))
+}
+
+/// Get the connected accounts from the Ethereum provider.
+/// Fails with `PROVIDER_NOT_INSTALLED` when no wallet is installed and with
+/// `NOT_CONNECTED` when the wallet is installed but locked or not yet connected to this site.
+pub async fn get_accounts() -> Result<Vec<String>, JsValue> {
+    let accounts = authorized_accounts(&get_provider()?).await?;
+    if accounts.is_empty() {
+        return Err(RustWasmError::NotConnected.into());
+    }
+    Ok(accounts)
+}
+
+/// The provider's `eth_accounts`, empty when the wallet is locked or not connected
+async fn authorized_accounts(provider: &JsValue) -> Result<Vec<String>, JsValue> {
+    let accounts = request_with_provider(provider, "eth_accounts", js_sys::Array::new(), &|_| None).await?
+        .dyn_into::<js_sys::Array>()
+        .map_err(|_| JsValue::from_str("Failed to parse accounts"))?;
+    Ok(accounts.iter().filter_map(|account| account.as_string()).collect())
+}
+
+/// Connect to the Ethereum provider and request access to accounts.
+/// Fails with `PROVIDER_NOT_INSTALLED` when no wallet is installed and with
+/// `NOT_CONNECTED` when the wallet grants no accounts.
 pub async fn connect() -> Result<Vec<String>, JsValue> {
     let provider = get_provider()?;
     
@@ -81,7 +379,8 @@ pub async fn connect() -> Result<Vec<String>, JsValue> {
         
         let promise = request_fn.call1(&provider, &args)?;
         let promise = js_sys::Promise::from(promise);
-        let accounts = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        let accounts = wasm_bindgen_futures::JsFuture::from(promise).await
+            .map_err(|e| JsValue::from(RustWasmError::from_provider_error(&e)))?;
         
         if let Ok(accounts_array) = accounts.dyn_into::<js_sys::Array>() {
             let mut result = Vec::new();
@@ -90,6 +389,9 @@ pub async fn connect() -> Result<Vec<String>, JsValue> {
                     result.push(account);
                 }
             }
+            if result.is_empty() {
+                return Err(RustWasmError::NotConnected.into());
+            }
             Ok(result)
         } else {
             Err(JsValue::from_str("Failed to parse accounts"))
@@ -103,6 +405,58 @@ pub async fn connect() -> Result<Vec<String>, JsValue> {
     }
 }
 
+/// Returns the accounts already authorized for this site without prompting, and only
+/// falls back to `connect` (which prompts the user) when there are none.
+pub async fn ensure_connected() -> Result<Vec<String>, JsValue> {
+    let accounts = authorized_accounts(&get_provider()?).await?;
+    if !accounts.is_empty() {
+        return Ok(accounts);
+    }
+    
+    connect().await
+}
+
+/// Returns the first connected account.
+/// Fails with `PROVIDER_NOT_INSTALLED` when no wallet is installed and with
+/// `NOT_CONNECTED` when the wallet exposes no accounts (locked or not connected).
+pub async fn require_account() -> Result<String, JsValue> {
+    get_accounts().await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| RustWasmError::NotConnected.into())
+}
+
+/// Like `require_account`, but asks an explicit provider
+pub(crate) async fn require_account_with_provider(provider: &JsValue) -> Result<String, JsValue> {
+    request_with_provider(provider, "eth_accounts", js_sys::Array::new(), &|_| None).await?
+        .dyn_into::<js_sys::Array>()
+        .map_err(|_| JsValue::from_str("Failed to parse accounts"))?
+        .get(0)
+        .as_string()
+        .ok_or_else(|| RustWasmError::NotConnected.into())
+}
+
+/// The wallet's active account, checksummed, or None when not connected. Reads the
+/// provider's `selectedAddress` when it has one and otherwise the first of `eth_accounts`.
+#[wasm_bindgen]
+pub async fn get_selected_account() -> Result<Option<String>, JsValue> {
+    let provider = get_provider()?;
+    
+    let selected = js_sys::Reflect::get(&provider, &JsValue::from_str("selectedAddress"))?
+        .as_string()
+        .filter(|address| !address.is_empty());
+    let account = match selected {
+        Some(address) => Some(address),
+        None => request_with_provider(&provider, "eth_accounts", js_sys::Array::new(), &|_| None).await?
+            .dyn_into::<js_sys::Array>()
+            .map_err(|_| JsValue::from_str("Failed to parse accounts"))?
+            .get(0)
+            .as_string(),
+    };
+    
+    account.map(|address| crate::utils::normalize_address(&address)).transpose()
+}
+
 /// Get the current network ID from the Ethereum provider
 pub async fn get_network_id() -> Result<u64, JsValue> {
     let provider = get_provider()?;
@@ -117,7 +471,8 @@ pub async fn get_network_id() -> Result<u64, JsValue> {
         
         let promise = request_fn.call1(&provider, &args)?;
         let promise = js_sys::Promise::from(promise);
-        let network_id = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        let network_id = wasm_bindgen_futures::JsFuture::from(promise).await
+            .map_err(|e| JsValue::from(RustWasmError::from_provider_error(&e)))?;
         
         if let Some(id_str) = network_id.as_string() {
             match id_str.parse::<u64>() {
@@ -136,47 +491,47 @@ pub async fn get_network_id() -> Result<u64, JsValue> {
     }
 }
 
-// Get Ethereum network information
+/// Get the chain ID of the connected network, parsed from the `eth_chainId` hex result
 #[wasm_bindgen]
-pub async fn get_network_info() -> Result<JsValue, JsValue> {
-    #[cfg(target_arch = "wasm32")]
-    {
-        let window = window().ok_or_else(|| JsValue::from_str("Window not available"))?;
-        
-        // Check for the existence of Ethereum provider
-        if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-            return Err(JsValue::from_str("Ethereum provider not found"));
-        }
+pub async fn get_chain_id() -> Result<u64, JsValue> {
+    let chain_id = request("eth_chainId", js_sys::Array::new()).await?;
+    parse_hex_quantity(&chain_id)
+}
 
-        // Get the Ethereum object
-        let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
-        
-        // Call the request method to get chainId
-        let request_fn = js_sys::Reflect::get(&ethereum, &JsValue::from_str("request"))?;
-        let request_fn = js_sys::Function::from(request_fn);
-        
-        let args = js_sys::Object::new();
-        js_sys::Reflect::set(&args, &JsValue::from_str("method"), &JsValue::from_str("eth_chainId"))?;
-        
-        // Create a Promise object and convert it to JsFuture
-        let promise = request_fn.call1(&ethereum, &args)?;
-        let promise = js_sys::Promise::from(promise);
-        let chain_id_result = wasm_bindgen_futures::JsFuture::from(promise).await?;
-        
-        // Return directly as JsValue to be processed on JavaScript side
-        let network_info = js_sys::Object::new();
-        js_sys::Reflect::set(&network_info, &JsValue::from_str("chainId"), &chain_id_result)?;
-        
-        Ok(network_info.into())
+/// Get a display name for the connected network, e.g. "Ethereum Mainnet" or "Unknown (1234)"
+#[wasm_bindgen]
+pub async fn get_network_name() -> Result<String, JsValue> {
+    Ok(crate::utils::network_name(get_chain_id().await?))
+}
+
+/// Errors if the connected chain differs from the one the transaction is intended for
+fn check_chain_id(expected: u64, connected: u64) -> Result<(), String> {
+    if expected != connected {
+        return Err(format!(
+            "Chain ID mismatch: transaction is for chain {} but the provider is connected to chain {}",
+            expected, connected
+        ));
     }
+    Ok(())
+}
 
+// Get Ethereum network information as { chainId, chainIdHex, networkName },
+// e.g. { chainId: 1, chainIdHex: "0x1", networkName: "Ethereum Mainnet" }
+#[wasm_bindgen]
+pub async fn get_network_info() -> Result<JsValue, JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    let chain_id = get_chain_id().await?;
+
+    // Fixed network info for testing (Ethereum mainnet)
     #[cfg(not(target_arch = "wasm32"))]
-    {
-        // Return fixed network info for testing (Ethereum mainnet)
-        let network_info = js_sys::Object::new();
-        js_sys::Reflect::set(&network_info, &JsValue::from_str("chainId"), &JsValue::from_str("0x1"))?;
-        Ok(network_info.into())
-    }
+    let chain_id = 1u64;
+
+    let network_info = js_sys::Object::new();
+    js_sys::Reflect::set(&network_info, &JsValue::from_str("chainId"), &JsValue::from_f64(chain_id as f64))?;
+    js_sys::Reflect::set(&network_info, &JsValue::from_str("chainIdHex"), &JsValue::from_str(&format!("0x{:x}", chain_id)))?;
+    js_sys::Reflect::set(&network_info, &JsValue::from_str("networkName"), &JsValue::from_str(&crate::utils::network_name(chain_id)))?;
+
+    Ok(network_info.into())
 }
 
 // Send transaction
@@ -184,15 +539,7 @@ pub async fn get_network_info() -> Result<JsValue, JsValue> {
 pub async fn send_transaction(tx_data: TransactionData) -> Result<String, JsValue> {
     #[cfg(target_arch = "wasm32")]
     {
-        let window = window().ok_or_else(|| JsValue::from_str("Window not available"))?;
-        
-        // Check for the existence of Ethereum provider
-        if !js_sys::Reflect::has(&window, &JsValue::from_str("ethereum")).unwrap_or(false) {
-            return Err(JsValue::from_str("Ethereum provider not found"));
-        }
-
-        // Get the Ethereum object
-        let ethereum = js_sys::Reflect::get(&window, &JsValue::from_str("ethereum"))?;
+        let ethereum = get_provider()?;
         
         // Create transaction object
         let tx_object = js_sys::Object::new();
@@ -202,6 +549,12 @@ pub async fn send_transaction(tx_data: TransactionData) -> Result<String, JsValu
         js_sys::Reflect::set(&tx_object, &JsValue::from_str("gas"), &JsValue::from_f64(tx_data.gas as f64))?;
         js_sys::Reflect::set(&tx_object, &JsValue::from_str("data"), &JsValue::from_str(&tx_data.data))?;
         
+        // Guard against replaying the transaction on a different chain
+        if let Some(chain_id) = tx_data.chain_id {
+            check_chain_id(chain_id, get_chain_id().await?).map_err(|e| JsValue::from_str(&e))?;
+            js_sys::Reflect::set(&tx_object, &JsValue::from_str("chainId"), &JsValue::from_str(&format!("0x{:x}", chain_id)))?;
+        }
+        
         // Send the transaction
         let request_fn = js_sys::Reflect::get(&ethereum, &JsValue::from_str("request"))?;
         let request_fn = js_sys::Function::from(request_fn);
@@ -213,7 +566,8 @@ pub async fn send_transaction(tx_data: TransactionData) -> Result<String, JsValu
         // Create a Promise object and convert it to JsFuture
         let promise = request_fn.call1(&ethereum, &args)?;
         let promise = js_sys::Promise::from(promise);
-        let tx_result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+        let tx_result = with_timeout(promise, request_timeout()).await?
+            .map_err(|e| JsValue::from(RustWasmError::from_provider_error(&e)))?;
         
         // Return the transaction hash
         let tx_hash = tx_result.as_string().ok_or_else(|| JsValue::from_str("Failed to get transaction hash"))?;
@@ -227,3 +581,581 @@ pub async fn send_transaction(tx_data: TransactionData) -> Result<String, JsValu
         Ok("0x".to_string() + &"1234567890abcdef".repeat(4))
     }
 }
+
+/// Sign a transaction with a local private key and submit it to the given RPC endpoint.
+/// Intended for native/server-side use where no wallet provider is available;
+/// nonce, gas price and (when `gas` is 0) the gas limit are filled in from the node.
+#[wasm_bindgen]
+pub async fn send_raw_transaction(private_key: String, tx: TransactionData, rpc_url: String) -> Result<String, JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (private_key, tx, rpc_url);
+        Err(JsValue::from_str("send_raw_transaction is not available in browser, use send_transaction instead"))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let client = NativeClient::new(&rpc_url).map_err(|e| JsValue::from_str(&e))?;
+        client.send_raw_transaction(&private_key, &tx).await.map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+/// Wait for the given number of milliseconds
+pub async fn sleep_ms(delay_ms: u32) -> Result<(), JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            if let Some(window) = window() {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    &resolve,
+                    delay_ms as i32
+                );
+            }
+        });
+        
+        wasm_bindgen_futures::JsFuture::from(promise).await?;
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms as u64)).await;
+    }
+    
+    Ok(())
+}
+
+/// Set the default timeout in milliseconds for provider requests such as calls, gas
+/// estimates and sending transactions. `0` (the default) waits for the provider indefinitely.
+#[wasm_bindgen]
+pub fn set_request_timeout(timeout_ms: u32) {
+    REQUEST_TIMEOUT_MS.with(|timeout| timeout.set(timeout_ms));
+}
+
+/// The default provider request timeout in milliseconds, `0` when requests never time out
+#[wasm_bindgen]
+pub fn request_timeout() -> u32 {
+    REQUEST_TIMEOUT_MS.with(|timeout| timeout.get())
+}
+
+/// Await a provider promise, giving up with `RustWasmError::Timeout` after `timeout_ms`
+/// (`0` waits indefinitely). The inner result is the promise's own outcome, so callers
+/// can map provider errors without touching the timeout.
+pub(crate) async fn with_timeout(promise: js_sys::Promise, timeout_ms: u32) -> Result<Result<JsValue, JsValue>, RustWasmError> {
+    if timeout_ms == 0 {
+        return Ok(wasm_bindgen_futures::JsFuture::from(promise).await);
+    }
+    
+    // Race the request against a timer that resolves with a marker object
+    let timed_out = JsValue::from(js_sys::Object::new());
+    let timer = js_sys::Promise::new(&mut |resolve, _| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .ok()
+            .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+        if let Some(set_timeout) = set_timeout {
+            // setTimeout passes its extra arguments on to the callback
+            let _ = set_timeout.call3(&JsValue::null(), &resolve, &JsValue::from_f64(timeout_ms as f64), &timed_out);
+        }
+    });
+    
+    let race = js_sys::Promise::race(&js_sys::Array::of2(&promise, &timer));
+    match wasm_bindgen_futures::JsFuture::from(race).await {
+        Ok(value) if value == timed_out => Err(RustWasmError::Timeout(timeout_ms)),
+        result => Ok(result),
+    }
+}
+
+/// Parse a 0x-prefixed hex quantity returned by the provider into a u64
+fn parse_hex_quantity(value: &JsValue) -> Result<u64, JsValue> {
+    let hex_str = value.as_string()
+        .ok_or_else(|| JsValue::from_str("Expected a hex quantity"))?;
+    crate::contract::normalize_hex_digits(&hex_str).ok()
+        .filter(|digits| !digits.is_empty())
+        .and_then(|digits| u64::from_str_radix(&digits, 16).ok())
+        .ok_or_else(|| JsValue::from_str(&format!("Failed to parse hex quantity: {}", hex_str)))
+}
+
+/// Parse a 0x-prefixed hex quantity returned by the provider into a U256
+fn parse_hex_u256(value: &JsValue) -> Result<U256, JsValue> {
+    let hex_str = value.as_string()
+        .ok_or_else(|| JsValue::from_str("Expected a hex quantity"))?;
+    crate::contract::normalize_hex_digits(&hex_str).ok()
+        .filter(|digits| !digits.is_empty())
+        .and_then(|digits| U256::from_str_radix(&digits, 16).ok())
+        .ok_or_else(|| JsValue::from_str(&format!("Failed to parse hex quantity: {}", hex_str)))
+}
+
+/// Get the latest block number
+pub async fn get_block_number() -> Result<u64, JsValue> {
+    get_block_number_with_provider(&get_provider()?).await
+}
+
+/// Get the latest block number from an explicit provider
+pub(crate) async fn get_block_number_with_provider(provider: &JsValue) -> Result<u64, JsValue> {
+    let block_number = request_with_provider(provider, "eth_blockNumber", js_sys::Array::new(), &|_| None).await?;
+    parse_hex_quantity(&block_number)
+}
+
+/// Block fields that get_block converts from hex quantities to decimal strings
+const BLOCK_DECIMAL_FIELDS: [&str; 5] = ["number", "timestamp", "gasUsed", "gasLimit", "baseFeePerGas"];
+
+/// Fetch a block by number or tag (normalized like other block parameters), or by its
+/// 32-byte hash. With `full_tx` the `transactions` array holds transaction objects rather
+/// than hashes. `number`, `timestamp`, `gasUsed`, `gasLimit` and `baseFeePerGas` are returned
+/// as decimal strings; other fields are left as the node sent them. Resolves to null for unknown blocks.
+#[wasm_bindgen]
+pub async fn get_block(block_tag: JsValue, full_tx: bool) -> Result<JsValue, JsValue> {
+    let block_hash = block_tag.as_string().filter(|tag| {
+        tag.len() == 66 && crate::contract::strip_hex_prefix(tag).is_some_and(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+    });
+    let (method, block_param) = match block_hash {
+        Some(hash) => ("eth_getBlockByHash", hash),
+        None => ("eth_getBlockByNumber", crate::utils::normalize_block_tag(&block_tag)?),
+    };
+    
+    let block = read_request(method, js_sys::Array::of2(&JsValue::from_str(&block_param), &JsValue::from_bool(full_tx))).await?;
+    if block.is_null() || block.is_undefined() {
+        return Ok(JsValue::NULL);
+    }
+    
+    for field in BLOCK_DECIMAL_FIELDS {
+        let key = JsValue::from_str(field);
+        let value = js_sys::Reflect::get(&block, &key)?;
+        // baseFeePerGas is missing on pre-London blocks
+        if value.is_undefined() || value.is_null() {
+            continue;
+        }
+        js_sys::Reflect::set(&block, &key, &JsValue::from_str(&parse_hex_u256(&value)?.to_string()))?;
+    }
+    
+    Ok(block)
+}
+
+/// How often subscribe_new_blocks polls eth_blockNumber when eth_subscribe is unavailable
+const NEW_BLOCK_POLL_MS: u32 = 4000;
+
+/// Calls `callback(error, header)` with the header of every new block. Uses
+/// `eth_subscribe("newHeads")` when the provider supports it and otherwise polls
+/// eth_blockNumber, fetching each new block with eth_getBlockByNumber. Returns a
+/// handle to pass to `unsubscribe_blocks`.
+#[wasm_bindgen]
+pub async fn subscribe_new_blocks(callback: &js_sys::Function) -> Result<JsValue, JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let ethereum = get_provider()?;
+        
+        let subscribe = js_sys::Function::new_with_args(
+            "ethereum, callback, pollMs",
+            r#"
+            return (async () => {
+                if (typeof ethereum.on === 'function') {
+                    try {
+                        const id = await ethereum.request({ method: 'eth_subscribe', params: ['newHeads'] });
+                        const listener = (message) => {
+                            if (message && message.type === 'eth_subscription' && message.data
+                                && message.data.subscription === id) {
+                                callback(null, message.data.result);
+                            }
+                        };
+                        ethereum.on('message', listener);
+                        return { kind: 'subscription', id, ethereum, listener };
+                    } catch (error) {
+                        // Fall back to polling below
+                    }
+                }
+                
+                let lastBlock = null;
+                const poll = async () => {
+                    try {
+                        const blockNumber = await ethereum.request({ method: 'eth_blockNumber', params: [] });
+                        if (lastBlock === null) {
+                            lastBlock = BigInt(blockNumber);
+                            return;
+                        }
+                        // Report every block since the last poll, in order
+                        while (lastBlock < BigInt(blockNumber)) {
+                            lastBlock += 1n;
+                            const block = await ethereum.request({
+                                method: 'eth_getBlockByNumber',
+                                params: ['0x' + lastBlock.toString(16), false]
+                            });
+                            callback(null, block);
+                        }
+                    } catch (error) {
+                        callback(error, null);
+                    }
+                };
+                
+                poll();
+                return { kind: 'polling', intervalId: setInterval(poll, pollMs) };
+            })();
+            "#
+        );
+        
+        let promise = subscribe.call3(&JsValue::null(), &ethereum, callback, &JsValue::from_f64(NEW_BLOCK_POLL_MS as f64))?;
+        wasm_bindgen_futures::JsFuture::from(js_sys::Promise::from(promise)).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (callback, NEW_BLOCK_POLL_MS);
+        // Mock subscription handle for testing
+        Ok(JsValue::from_str("0x1"))
+    }
+}
+
+/// Stops a block subscription created by `subscribe_new_blocks`
+#[wasm_bindgen]
+pub async fn unsubscribe_blocks(handle: JsValue) -> Result<(), JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if handle.is_null() || handle.is_undefined() {
+            return Ok(());
+        }
+        
+        let kind = js_sys::Reflect::get(&handle, &JsValue::from_str("kind"))?.as_string();
+        match kind.as_deref() {
+            Some("subscription") => {
+                let ethereum = js_sys::Reflect::get(&handle, &JsValue::from_str("ethereum"))?;
+                let listener = js_sys::Reflect::get(&handle, &JsValue::from_str("listener"))?;
+                let remove_listener = js_sys::Reflect::get(&ethereum, &JsValue::from_str("removeListener"))?;
+                if let Some(remove_listener) = remove_listener.dyn_ref::<js_sys::Function>() {
+                    remove_listener.call2(&ethereum, &JsValue::from_str("message"), &listener)?;
+                }
+                
+                let id = js_sys::Reflect::get(&handle, &JsValue::from_str("id"))?;
+                request_with_provider(&ethereum, "eth_unsubscribe", js_sys::Array::of1(&id), &|_| None).await?;
+            },
+            Some("polling") => {
+                let window = window().ok_or_else(|| JsValue::from_str("Could not access window"))?;
+                let interval_id = js_sys::Reflect::get(&handle, &JsValue::from_str("intervalId"))?;
+                let clear_interval = js_sys::Reflect::get(&window, &JsValue::from_str("clearInterval"))?;
+                js_sys::Function::from(clear_interval).call1(&JsValue::null(), &interval_id)?;
+            },
+            _ => return Err(JsValue::from_str("Invalid block subscription handle")),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = handle;
+    }
+    
+    Ok(())
+}
+
+/// Get the fee history of the last `block_count` blocks (1 to 1024) with eth_feeHistory, for
+/// deriving slow/average/fast fee tiers. `reward_percentiles` is an optional ascending array
+/// of numbers from 0 to 100. Returns `{ oldestBlock, baseFeePerGas, gasUsedRatio, reward }`
+/// with all quantities as decimal strings; `reward` holds one array per block.
+#[wasm_bindgen]
+pub async fn get_fee_history(block_count: u32, reward_percentiles: JsValue) -> Result<JsValue, JsValue> {
+    if block_count == 0 || block_count > 1024 {
+        return Err(JsValue::from_str(&format!("Block count must be between 1 and 1024, got {}", block_count)));
+    }
+    
+    let percentiles = js_sys::Array::new();
+    if !reward_percentiles.is_undefined() && !reward_percentiles.is_null() {
+        if !js_sys::Array::is_array(&reward_percentiles) {
+            return Err(JsValue::from_str("Reward percentiles must be an array of numbers"));
+        }
+        let mut previous = 0.0;
+        for percentile in js_sys::Array::from(&reward_percentiles).iter() {
+            let value = percentile.as_f64()
+                .filter(|value| (previous..=100.0).contains(value))
+                .ok_or_else(|| JsValue::from_str("Reward percentiles must be ascending numbers between 0 and 100"))?;
+            previous = value;
+            percentiles.push(&percentile);
+        }
+    }
+    
+    let params = js_sys::Array::new();
+    params.push(&JsValue::from_str(&format!("0x{:x}", block_count)));
+    params.push(&JsValue::from_str("latest"));
+    params.push(&percentiles);
+    let history = request("eth_feeHistory", params).await?;
+    
+    let field = |key: &str| js_sys::Reflect::get(&history, &JsValue::from_str(key));
+    let to_decimal = |value: &JsValue| -> Result<JsValue, JsValue> {
+        Ok(JsValue::from_str(&parse_hex_u256(value)?.to_string()))
+    };
+    let to_decimal_array = |value: &JsValue| -> Result<JsValue, JsValue> {
+        let array = js_sys::Array::new();
+        if js_sys::Array::is_array(value) {
+            for item in js_sys::Array::from(value).iter() {
+                array.push(&to_decimal(&item)?);
+            }
+        }
+        Ok(array.into())
+    };
+    
+    let reward = js_sys::Array::new();
+    let block_rewards = field("reward")?;
+    if js_sys::Array::is_array(&block_rewards) {
+        for block_reward in js_sys::Array::from(&block_rewards).iter() {
+            reward.push(&to_decimal_array(&block_reward)?);
+        }
+    }
+    
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &JsValue::from_str("oldestBlock"), &to_decimal(&field("oldestBlock")?)?)?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("baseFeePerGas"), &to_decimal_array(&field("baseFeePerGas")?)?)?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("gasUsedRatio"), &field("gasUsedRatio")?)?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("reward"), &reward)?;
+    
+    Ok(result.into())
+}
+
+/// Blocks of fee history used to suggest the EIP-1559 priority fee
+const FEE_HISTORY_BLOCKS: u32 = 5;
+
+/// Suggest gas settings for a transaction under both pricing models, for a confirmation screen:
+/// `{ gasLimit, legacy: { gasPrice }, eip1559: { maxFeePerGas, maxPriorityFeePerGas } }` with
+/// decimal values. The gas limit is the eth_estimateGas estimate plus the default buffer; the
+/// priority fee is the median tip of recent blocks and the max fee allows the base fee to double.
+/// `eip1559` is null on chains without a base fee.
+#[wasm_bindgen]
+pub async fn suggest_gas_options(tx: TransactionData) -> Result<JsValue, JsValue> {
+    let tx_object = js_sys::Object::new();
+    if !tx.from.is_empty() {
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("from"), &JsValue::from_str(&crate::utils::normalize_address(&tx.from)?))?;
+    }
+    if !tx.to.is_empty() {
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("to"), &JsValue::from_str(&crate::utils::normalize_address(&tx.to)?))?;
+    }
+    if !tx.value.is_empty() {
+        let value = crate::utils::parse_quantity(&tx.value, "value")?;
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("value"), &JsValue::from_str(&format!("0x{:x}", value)))?;
+    }
+    if !tx.data.is_empty() {
+        js_sys::Reflect::set(&tx_object, &JsValue::from_str("data"), &JsValue::from_str(&tx.data))?;
+    }
+    
+    let estimate = parse_hex_quantity(&request("eth_estimateGas", js_sys::Array::of1(&tx_object)).await?)?;
+    let gas_limit = crate::utils::apply_gas_buffer(estimate, crate::utils::DEFAULT_GAS_BUFFER_PERCENT)?;
+    let gas_price = parse_hex_u256(&request("eth_gasPrice", js_sys::Array::new()).await?)?;
+    
+    let legacy = js_sys::Object::new();
+    js_sys::Reflect::set(&legacy, &JsValue::from_str("gasPrice"), &JsValue::from_str(&gas_price.to_string()))?;
+    
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &JsValue::from_str("gasLimit"), &JsValue::from_str(&gas_limit.to_string()))?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("legacy"), &legacy)?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("eip1559"), &suggest_eip1559_fees().await?)?;
+    
+    Ok(result.into())
+}
+
+/// `{ maxFeePerGas, maxPriorityFeePerGas }` from recent fee history, or null when the chain
+/// reports no base fee (or does not support eth_feeHistory)
+async fn suggest_eip1559_fees() -> Result<JsValue, JsValue> {
+    let params = js_sys::Array::new();
+    params.push(&JsValue::from_str(&format!("0x{:x}", FEE_HISTORY_BLOCKS)));
+    params.push(&JsValue::from_str("latest"));
+    params.push(&js_sys::Array::of1(&JsValue::from_f64(50.0)));
+    let history = match request("eth_feeHistory", params).await {
+        Ok(history) => history,
+        Err(_) => return Ok(JsValue::NULL),
+    };
+    
+    // The last base fee is the one predicted for the next block
+    let base_fees = js_sys::Reflect::get(&history, &JsValue::from_str("baseFeePerGas"))?;
+    let next_base_fee = match js_sys::Array::is_array(&base_fees).then(|| js_sys::Array::from(&base_fees).pop()) {
+        Some(base_fee) if !base_fee.is_undefined() && !base_fee.is_null() => parse_hex_u256(&base_fee)?,
+        _ => return Ok(JsValue::NULL),
+    };
+    if next_base_fee.is_zero() {
+        return Ok(JsValue::NULL);
+    }
+    
+    // Median of each block's median tip
+    let mut tips = Vec::new();
+    let rewards = js_sys::Reflect::get(&history, &JsValue::from_str("reward"))?;
+    if js_sys::Array::is_array(&rewards) {
+        for block_reward in js_sys::Array::from(&rewards).iter() {
+            if js_sys::Array::is_array(&block_reward) {
+                tips.push(parse_hex_u256(&js_sys::Array::from(&block_reward).get(0))?);
+            }
+        }
+    }
+    tips.sort();
+    let priority_fee = match tips.get(tips.len() / 2) {
+        Some(tip) => *tip,
+        // Without reward data, ask the node for its suggestion
+        None => match request("eth_maxPriorityFeePerGas", js_sys::Array::new()).await {
+            Ok(tip) => parse_hex_u256(&tip)?,
+            Err(_) => U256::zero(),
+        },
+    };
+    
+    let max_fee = next_base_fee.saturating_mul(U256::from(2)).saturating_add(priority_fee);
+    
+    let fees = js_sys::Object::new();
+    js_sys::Reflect::set(&fees, &JsValue::from_str("maxFeePerGas"), &JsValue::from_str(&max_fee.to_string()))?;
+    js_sys::Reflect::set(&fees, &JsValue::from_str("maxPriorityFeePerGas"), &JsValue::from_str(&priority_fee.to_string()))?;
+    Ok(fees.into())
+}
+
+/// Get the receipt of a transaction, or null if it has not been mined yet
+pub async fn get_transaction_receipt(tx_hash: &str) -> Result<JsValue, JsValue> {
+    request("eth_getTransactionReceipt", js_sys::Array::of1(&JsValue::from_str(tx_hash))).await
+}
+
+/// Receipt polling parameters: the delay before the second poll, the cap the
+/// exponentially growing delay is held at, and the number of polls before giving up.
+/// Defaults to 1s, 10s and 50 attempts.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PollConfig {
+    initial_delay_ms: u32,
+    max_delay_ms: u32,
+    max_attempts: u32,
+}
+
+#[wasm_bindgen]
+impl PollConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(initial_delay_ms: u32, max_delay_ms: u32, max_attempts: u32) -> Result<PollConfig, JsValue> {
+        if initial_delay_ms == 0 || max_delay_ms == 0 || max_attempts == 0 {
+            return Err(JsValue::from_str("Poll delays and max attempts must be positive"));
+        }
+        if initial_delay_ms > max_delay_ms {
+            return Err(JsValue::from_str(&format!(
+                "Initial poll delay ({}ms) exceeds the maximum delay ({}ms)", initial_delay_ms, max_delay_ms
+            )));
+        }
+        
+        Ok(PollConfig {
+            initial_delay_ms,
+            max_delay_ms,
+            max_attempts,
+        })
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn initial_delay_ms(&self) -> u32 {
+        self.initial_delay_ms
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn max_delay_ms(&self) -> u32 {
+        self.max_delay_ms
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    
+    /// The delay to wait after a poll, doubling from the initial delay up to the cap
+    pub(crate) fn next_delay(&self, delay_ms: u32) -> u32 {
+        delay_ms.saturating_mul(2).min(self.max_delay_ms)
+    }
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        PollConfig {
+            initial_delay_ms: 1000,
+            max_delay_ms: 10000,
+            max_attempts: 50,
+        }
+    }
+}
+
+/// Poll for a transaction receipt through `provider` with exponential backoff until the
+/// transaction has the requested number of confirmations (the mined block counts as one)
+pub async fn wait_for_receipt(provider: &JsValue, tx_hash: &str, confirmations: u32, poll_config: &PollConfig) -> Result<JsValue, JsValue> {
+    let mut attempts = 0;
+    let mut delay_ms = poll_config.initial_delay_ms;
+    
+    while attempts < poll_config.max_attempts {
+        let receipt = request_with_provider(provider, "eth_getTransactionReceipt", js_sys::Array::of1(&JsValue::from_str(tx_hash)), &|_| None).await?;
+        
+        if !receipt.is_null() && !receipt.is_undefined() {
+            if confirmations <= 1 {
+                return Ok(receipt);
+            }
+            
+            let receipt_block = parse_hex_quantity(&js_sys::Reflect::get(&receipt, &JsValue::from_str("blockNumber"))?)?;
+            let current_block = parse_hex_quantity(&request_with_provider(provider, "eth_blockNumber", js_sys::Array::new(), &|_| None).await?)?;
+            if current_block.saturating_sub(receipt_block) + 1 >= confirmations as u64 {
+                return Ok(receipt);
+            }
+        }
+        
+        sleep_ms(delay_ms).await?;
+        
+        attempts += 1;
+        delay_ms = poll_config.next_delay(delay_ms);
+    }
+    
+    Err(JsValue::from_str("Transaction receipt not found after maximum attempts"))
+}
+
+/// Wait for a transaction to be mined and report whether it succeeded.
+/// Returns `{ transactionHash, status, blockNumber, gasUsed }` where status is
+/// "success" or "reverted"; reverted transactions also carry a `revertReason`
+/// when it can be recovered by replaying the call. Polling uses the default `PollConfig`
+/// unless one is given.
+#[wasm_bindgen]
+pub async fn wait_for_transaction_status(tx_hash: String, confirmations: u32, poll_config: Option<PollConfig>) -> Result<JsValue, JsValue> {
+    let provider = get_provider()?;
+    let receipt = wait_for_receipt(&provider, &tx_hash, confirmations, &poll_config.unwrap_or_default()).await?;
+    Ok(receipt_status(&provider, &tx_hash, &receipt).await?.into())
+}
+
+/// Build the `{ transactionHash, status, blockNumber, gasUsed, revertReason? }` summary of a mined receipt
+pub async fn receipt_status(provider: &JsValue, tx_hash: &str, receipt: &JsValue) -> Result<js_sys::Object, JsValue> {
+    let status = js_sys::Reflect::get(receipt, &JsValue::from_str("status"))?;
+    let block_number = js_sys::Reflect::get(receipt, &JsValue::from_str("blockNumber"))?;
+    let gas_used = js_sys::Reflect::get(receipt, &JsValue::from_str("gasUsed"))?;
+    let succeeded = status.as_string().as_deref() == Some("0x1");
+    
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &JsValue::from_str("transactionHash"), &JsValue::from_str(tx_hash))?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("status"), &JsValue::from_str(if succeeded { "success" } else { "reverted" }))?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("blockNumber"), &JsValue::from_str(&parse_hex_quantity(&block_number)?.to_string()))?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("gasUsed"), &JsValue::from_str(&parse_hex_quantity(&gas_used)?.to_string()))?;
+    
+    if !succeeded {
+        if let Some(reason) = replay_revert_reason(provider, tx_hash, &block_number).await {
+            js_sys::Reflect::set(&result, &JsValue::from_str("revertReason"), &JsValue::from_str(&reason))?;
+        }
+    }
+    
+    Ok(result)
+}
+
+/// Replay a mined transaction as an eth_call at its block to recover the revert reason
+async fn replay_revert_reason(provider: &JsValue, tx_hash: &str, block_number: &JsValue) -> Option<String> {
+    let tx = request_with_provider(provider, "eth_getTransactionByHash", js_sys::Array::of1(&JsValue::from_str(tx_hash)), &|_| None).await.ok()?;
+    if tx.is_null() || tx.is_undefined() {
+        return None;
+    }
+    
+    let call_obj = js_sys::Object::new();
+    for (tx_key, call_key) in [("from", "from"), ("to", "to"), ("input", "data"), ("value", "value"), ("gas", "gas")] {
+        let value = js_sys::Reflect::get(&tx, &JsValue::from_str(tx_key)).ok()?;
+        if !value.is_null() && !value.is_undefined() {
+            js_sys::Reflect::set(&call_obj, &JsValue::from_str(call_key), &value).ok()?;
+        }
+    }
+    
+    match request_with_provider(provider, "eth_call", js_sys::Array::of2(&call_obj, block_number), &|_| None).await {
+        Ok(_) => None,
+        Err(error) => revert_reason_from_error(&error),
+    }
+}
+
+/// Extract a human-readable revert reason from a provider error; `request` has already
+/// decoded any revert data into the error message
+fn revert_reason_from_error(error: &JsValue) -> Option<String> {
+    let code = js_sys::Reflect::get(error, &JsValue::from_str("code")).ok()?.as_string();
+    let message = js_sys::Reflect::get(error, &JsValue::from_str("message")).ok()?.as_string()?;
+    
+    // Keep only the reason for reverts rather than the "Transaction reverted: " prefix
+    match code.as_deref() {
+        Some("REVERTED") => Some(message.trim_start_matches("Transaction reverted: ").to_string()),
+        _ => Some(message),
+    }
+}
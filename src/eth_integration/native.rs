@@ -0,0 +1,135 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use futures_util::future::join_all;
+use crate::TransactionData;
+use super::check_chain_id;
+
+/// Reusable JSON-RPC client for native (server-side) use.
+/// The HTTP provider is built once and shared, so cloning the client is cheap
+/// and no connection setup is repeated per call. Mirrors the wasm API surface.
+#[derive(Clone)]
+pub struct NativeClient {
+    provider: Arc<Provider<Http>>,
+}
+
+impl NativeClient {
+    /// Creates a client for the given RPC URL
+    pub fn new(rpc_url: &str) -> Result<NativeClient, String> {
+        let provider = Provider::<Http>::try_from(rpc_url)
+            .map_err(|e| format!("Invalid RPC URL: {}", e))?;
+
+        Ok(NativeClient {
+            provider: Arc::new(provider),
+        })
+    }
+
+    /// Returns the underlying provider
+    pub fn provider(&self) -> Arc<Provider<Http>> {
+        self.provider.clone()
+    }
+
+    /// Get the balance of an address in wei, as a decimal string
+    pub async fn get_balance(&self, address: &str) -> Result<String, String> {
+        let address = Address::from_str(address)
+            .map_err(|_| format!("Invalid Ethereum address: {}", address))?;
+
+        let balance = self.provider.get_balance(address, None).await
+            .map_err(|e| format!("Failed to get balance: {}", e))?;
+
+        Ok(balance.to_string())
+    }
+
+    /// Get the balances of several addresses in wei, as decimal strings in the same order.
+    /// The eth_getBalance requests are issued concurrently rather than one after another;
+    /// the first failure (e.g. an invalid address) is returned as the error.
+    pub async fn balances(&self, addresses: Vec<String>) -> Result<Vec<String>, String> {
+        join_all(addresses.iter().map(|address| self.get_balance(address)))
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the latest block number
+    pub async fn get_block_number(&self) -> Result<u64, String> {
+        let block_number = self.provider.get_block_number().await
+            .map_err(|e| format!("Failed to get block number: {}", e))?;
+
+        Ok(block_number.as_u64())
+    }
+
+    /// Get the chain ID of the connected network
+    pub async fn get_chain_id(&self) -> Result<u64, String> {
+        let chain_id = self.provider.get_chainid().await
+            .map_err(|e| format!("Failed to get chain ID: {}", e))?;
+
+        Ok(chain_id.as_u64())
+    }
+
+    /// Execute an eth_call with hex calldata against the given address and return the raw hex result
+    pub async fn call(&self, to: &str, data: &str) -> Result<String, String> {
+        let to = Address::from_str(to)
+            .map_err(|_| format!("Invalid Ethereum address: {}", to))?;
+        let data = Bytes::from_str(data)
+            .map_err(|_| format!("Invalid calldata: {}", data))?;
+
+        let tx: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+        let result = self.provider.call(&tx, None).await
+            .map_err(|e| format!("eth_call failed: {}", e))?;
+
+        Ok(format!("{}", result))
+    }
+
+    /// Sign a transaction with a local private key and submit it, returning the transaction hash.
+    /// Nonce, gas price and (when `gas` is 0) the gas limit are filled in from the node.
+    pub async fn send_raw_transaction(&self, private_key: &str, tx: &TransactionData) -> Result<String, String> {
+        let chain_id = self.get_chain_id().await?;
+        if let Some(expected) = tx.chain_id {
+            check_chain_id(expected, chain_id)?;
+        }
+
+        let wallet = LocalWallet::from_str(private_key.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid private key: {}", e))?
+            .with_chain_id(chain_id);
+
+        if !tx.from.is_empty() {
+            let from = Address::from_str(&tx.from)
+                .map_err(|_| format!("Invalid from address: {}", tx.from))?;
+            if from != wallet.address() {
+                return Err(format!(
+                    "Transaction from address {} does not match the private key address {:?}",
+                    tx.from, wallet.address()
+                ));
+            }
+        }
+
+        let mut request = TransactionRequest::new().from(wallet.address());
+        if !tx.to.is_empty() {
+            let to = Address::from_str(&tx.to)
+                .map_err(|_| format!("Invalid to address: {}", tx.to))?;
+            request = request.to(to);
+        }
+        if !tx.value.is_empty() {
+            let value = match tx.value.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(&tx.value).ok(),
+            }.ok_or_else(|| format!("Invalid value: {}", tx.value))?;
+            request = request.value(value);
+        }
+        if tx.gas > 0 {
+            request = request.gas(tx.gas);
+        }
+        if !tx.data.is_empty() && tx.data != "0x" {
+            let data = Bytes::from_str(&tx.data)
+                .map_err(|_| format!("Invalid transaction data: {}", tx.data))?;
+            request = request.data(data);
+        }
+
+        let client = SignerMiddleware::new(self.provider.clone(), wallet);
+        let pending = client.send_transaction(request, None).await
+            .map_err(|e| format!("Failed to send transaction: {}", e))?;
+
+        Ok(format!("{:#x}", pending.tx_hash()))
+    }
+}
@@ -6,12 +6,16 @@ mod eth_integration;
 mod utils;
 mod bindings;
 mod contract;
+mod error;
 
 // Export Ethereum integration
 pub use eth_integration::*;
+pub use error::RustWasmError;
 pub use crate::contract::{
     AbiItem, Contract, ERC20Token, 
-    ContractEventFilter, StateMutability, ContractDeployer
+    ContractEventFilter, StateMutability, ContractDeployer,
+    INTERFACE_ID_ERC165, INTERFACE_ID_ERC721, INTERFACE_ID_ERC721_METADATA, INTERFACE_ID_ERC721_ENUMERABLE,
+    INTERFACE_ID_ERC1155, INTERFACE_ID_ERC1155_METADATA_URI, INTERFACE_ID_ERC2981
 };
 
 // Basic web connection functions
@@ -22,12 +26,7 @@ use web_sys::{console, window};
 #[wasm_bindgen]
 pub async fn connect_to_ethereum() -> Result<String, JsValue> {
     match eth_integration::connect().await {
-        Ok(accounts) => {
-            if accounts.is_empty() {
-                return Ok("Connected to Ethereum but no accounts available".to_string());
-            }
-            Ok("Connected to Ethereum!".to_string())
-        },
+        Ok(_) => Ok("Connected to Ethereum!".to_string()),
         Err(e) => Err(e),
     }
 }
@@ -70,14 +69,36 @@ pub fn another_function() -> i32 {
     42
 }
 
-// Additional function that returns an error if dividing by zero
+// Additional function that returns an error if dividing by zero, or on the
+// overflow of i32::MIN / -1, instead of panicking (which aborts in wasm)
 #[wasm_bindgen]
 pub fn divide(x: i32, y: i32) -> Result<i32, JsValue> {
     if y == 0 {
-        Err(JsValue::from_str("Division by zero error"))
-    } else {
-        Ok(x / y)
+        return Err(JsValue::from_str("Division by zero error"));
     }
+    x.checked_div(y)
+        .ok_or_else(|| JsValue::from_str(&format!("Overflow: {} / {} does not fit in an i32", x, y)))
+}
+
+// Checked i32 addition, returning an error instead of overflowing
+#[wasm_bindgen]
+pub fn checked_add(x: i32, y: i32) -> Result<i32, JsValue> {
+    x.checked_add(y)
+        .ok_or_else(|| JsValue::from_str(&format!("Overflow: {} + {} does not fit in an i32", x, y)))
+}
+
+// Checked i32 subtraction, returning an error instead of overflowing
+#[wasm_bindgen]
+pub fn checked_sub(x: i32, y: i32) -> Result<i32, JsValue> {
+    x.checked_sub(y)
+        .ok_or_else(|| JsValue::from_str(&format!("Overflow: {} - {} does not fit in an i32", x, y)))
+}
+
+// Checked i32 multiplication, returning an error instead of overflowing
+#[wasm_bindgen]
+pub fn checked_mul(x: i32, y: i32) -> Result<i32, JsValue> {
+    x.checked_mul(y)
+        .ok_or_else(|| JsValue::from_str(&format!("Overflow: {} * {} does not fit in an i32", x, y)))
 }
 
 // Data structures for Ethereum transactions
@@ -89,6 +110,11 @@ pub struct TransactionData {
     value: String,
     gas: u64,
     data: String,
+    chain_id: Option<u64>,
+    nonce: Option<u64>,
+    gas_price: Option<String>,
+    max_fee_per_gas: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
 }
 
 // Structure to receive data from JavaScript
@@ -102,6 +128,11 @@ impl TransactionData {
             value,
             gas,
             data,
+            chain_id: None,
+            nonce: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
         }
     }
 
@@ -114,6 +145,61 @@ impl TransactionData {
     pub fn to(&self) -> String {
         self.to.clone()
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    // Chain the transaction is intended for, checked against the connected chain before sending
+    #[wasm_bindgen(setter)]
+    pub fn set_chain_id(&mut self, chain_id: Option<u64>) {
+        self.chain_id = chain_id;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    // Account nonce, used when building an unsigned transaction for offline signing
+    #[wasm_bindgen(setter)]
+    pub fn set_nonce(&mut self, nonce: Option<u64>) {
+        self.nonce = nonce;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn gas_price(&self) -> Option<String> {
+        self.gas_price.clone()
+    }
+
+    // Gas price in wei (decimal or 0x hex) for a legacy transaction
+    #[wasm_bindgen(setter)]
+    pub fn set_gas_price(&mut self, gas_price: Option<String>) {
+        self.gas_price = gas_price;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_fee_per_gas(&self) -> Option<String> {
+        self.max_fee_per_gas.clone()
+    }
+
+    // Maximum fee per gas in wei (decimal or 0x hex); setting it makes the transaction EIP-1559
+    #[wasm_bindgen(setter)]
+    pub fn set_max_fee_per_gas(&mut self, max_fee_per_gas: Option<String>) {
+        self.max_fee_per_gas = max_fee_per_gas;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_priority_fee_per_gas(&self) -> Option<String> {
+        self.max_priority_fee_per_gas.clone()
+    }
+
+    // Maximum priority fee (tip) per gas in wei (decimal or 0x hex) for an EIP-1559 transaction
+    #[wasm_bindgen(setter)]
+    pub fn set_max_priority_fee_per_gas(&mut self, max_priority_fee_per_gas: Option<String>) {
+        self.max_priority_fee_per_gas = max_priority_fee_per_gas;
+    }
 }
 
 // Helper function to pass error messages to JavaScript
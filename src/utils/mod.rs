@@ -1,18 +1,29 @@
 use wasm_bindgen::prelude::*;
 use serde_json::{Value, Error as JsonError};
+use primitive_types::U256;
+use crate::contract::{bytes_to_hex, hex_to_bytes, keccak256, normalize_hex_digits, strip_hex_prefix};
+use crate::error::RustWasmError;
+use crate::TransactionData;
 
 #[cfg(target_arch = "wasm32")]
 use web_sys::console;
 
+// Normalize hex to lowercase digits without the 0x/0X prefix, left-padded to an even
+// length, so RPC values such as 0x0, 0xA or 0XABC parse like clean even-length hex
+#[wasm_bindgen]
+pub fn normalize_hex(s: &str) -> Result<String, JsValue> {
+    normalize_hex_digits(s).map_err(|e| JsValue::from_str(&e))
+}
+
 // Convert hex format value to decimal format
 #[wasm_bindgen]
 pub fn hex_to_decimal(hex_value: &str) -> Result<String, JsValue> {
-    if !hex_value.starts_with("0x") {
+    if strip_hex_prefix(hex_value).is_none() {
         return Err(JsValue::from_str("Not a valid hex string, must start with 0x"));
     }
 
-    let hex_str = &hex_value[2..]; // Remove "0x" prefix
-    match u128::from_str_radix(hex_str, 16) {
+    let hex_str = normalize_hex(hex_value)?;
+    match u128::from_str_radix(&hex_str, 16) {
         Ok(num) => Ok(num.to_string()),
         Err(_) => Err(JsValue::from_str("Failed to convert hex to decimal"))
     }
@@ -40,6 +51,205 @@ pub fn wei_to_ether(wei_value: &str) -> Result<String, JsValue> {
     }
 }
 
+// Format a Wei value as Ether with exactly `decimals` fractional digits.
+// Uses integer arithmetic throughout; the cutoff digit is truncated unless `round`
+// is set, in which case it is rounded half-up.
+#[wasm_bindgen]
+pub fn format_ether(wei: &str, decimals: u8, round: bool) -> Result<String, JsValue> {
+    const ETHER_DECIMALS: usize = 18;
+
+    let wei_value = match strip_hex_prefix(wei) {
+        Some(hex) if !hex.is_empty() => normalize_hex_digits(hex).ok().and_then(|hex| U256::from_str_radix(&hex, 16).ok()),
+        Some(_) => None,
+        None if !wei.is_empty() && wei.chars().all(|c| c.is_ascii_digit()) => U256::from_dec_str(wei).ok(),
+        None => None,
+    }.ok_or_else(|| JsValue::from_str(&format!("Invalid wei value: {}", wei)))?;
+
+    let decimals = decimals as usize;
+    let (integer_part, fraction_part) = if decimals >= ETHER_DECIMALS {
+        // Nothing is cut off, the fraction is only padded
+        let one_ether = U256::exp10(ETHER_DECIMALS);
+        let fraction = format!("{:0>width$}", (wei_value % one_ether).to_string(), width = ETHER_DECIMALS);
+        (wei_value / one_ether, format!("{:0<width$}", fraction, width = decimals))
+    } else {
+        let unit = U256::exp10(ETHER_DECIMALS - decimals);
+        let mut scaled = wei_value / unit;
+        if round && (wei_value % unit) * 2 >= unit {
+            scaled += U256::one();
+        }
+        let scale = U256::exp10(decimals);
+        let fraction = format!("{:0>width$}", (scaled % scale).to_string(), width = decimals);
+        (scaled / scale, fraction)
+    };
+
+    if decimals == 0 {
+        Ok(integer_part.to_string())
+    } else {
+        Ok(format!("{}.{}", integer_part, fraction_part))
+    }
+}
+
+// Format a raw integer amount (decimal or 0x-prefixed hex) with the given number of decimals,
+// e.g. a token balance. Uses big-integer string math, so no precision is lost; the integer
+// part is always at least "0" and trailing fractional zeros are trimmed.
+#[wasm_bindgen]
+pub fn format_units(value: &str, decimals: u8) -> Result<String, JsValue> {
+    // Convert from hex if needed
+    let value_str = match strip_hex_prefix(value) {
+        Some(hex) => normalize_hex_digits(hex).ok()
+            .filter(|hex| !hex.is_empty())
+            .and_then(|hex| U256::from_str_radix(&hex, 16).ok())
+            .ok_or_else(|| JsValue::from_str(&format!("Invalid amount format: {}", value)))?
+            .to_string(),
+        None => value.to_string(),
+    };
+    
+    if value_str.is_empty() || !value_str.chars().all(|c| c.is_ascii_digit()) {
+        return Err(JsValue::from_str(&format!("Invalid amount format: {}", value)));
+    }
+    
+    // Split into integer and fractional digits, left-padding the fraction when the
+    // value has fewer digits than the number of decimals
+    let digits = value_str.trim_start_matches('0');
+    let decimals = decimals as usize;
+    let (integer_part, fraction_part) = if digits.len() > decimals {
+        let (integer_part, fraction_part) = digits.split_at(digits.len() - decimals);
+        (integer_part.to_string(), fraction_part.to_string())
+    } else {
+        (String::new(), format!("{:0>width$}", digits, width = decimals))
+    };
+    
+    let integer_part = if integer_part.is_empty() { "0".to_string() } else { integer_part };
+    
+    // Remove trailing zeros, and the decimal point when nothing is left after it
+    let fraction_part = fraction_part.trim_end_matches('0');
+    if fraction_part.is_empty() {
+        Ok(integer_part)
+    } else {
+        Ok(format!("{}.{}", integer_part, fraction_part))
+    }
+}
+
+// Add two integer amounts given as decimal strings (e.g. token balances in base units)
+#[wasm_bindgen]
+pub fn add_amounts(a: &str, b: &str) -> Result<String, JsValue> {
+    parse_amount_operand(a)?.checked_add(parse_amount_operand(b)?)
+        .map(|sum| sum.to_string())
+        .ok_or_else(|| JsValue::from_str(&format!("{} + {} overflows 256 bits", a, b)))
+}
+
+// Subtract `b` from `a`, erroring instead of going below zero
+#[wasm_bindgen]
+pub fn sub_amounts(a: &str, b: &str) -> Result<String, JsValue> {
+    parse_amount_operand(a)?.checked_sub(parse_amount_operand(b)?)
+        .map(|difference| difference.to_string())
+        .ok_or_else(|| JsValue::from_str(&format!("{} - {} is negative", a, b)))
+}
+
+// Multiply an amount by an integer factor, both decimal strings
+#[wasm_bindgen]
+pub fn mul_amount_by(amount: &str, factor: &str) -> Result<String, JsValue> {
+    parse_amount_operand(amount)?.checked_mul(parse_amount_operand(factor)?)
+        .map(|product| product.to_string())
+        .ok_or_else(|| JsValue::from_str(&format!("{} * {} overflows 256 bits", amount, factor)))
+}
+
+// Divide an amount by an integer divisor, both decimal strings, rounding down
+#[wasm_bindgen]
+pub fn div_amount_by(amount: &str, divisor: &str) -> Result<String, JsValue> {
+    parse_amount_operand(amount)?.checked_div(parse_amount_operand(divisor)?)
+        .map(|quotient| quotient.to_string())
+        .ok_or_else(|| JsValue::from_str("Division by zero"))
+}
+
+// Parse a non-negative decimal integer string into a U256
+fn parse_amount_operand(value: &str) -> Result<U256, JsValue> {
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(JsValue::from_str(&format!("Invalid amount: '{}' is not a decimal integer", value)));
+    }
+    U256::from_dec_str(value)
+        .map_err(|_| JsValue::from_str(&format!("Invalid amount: {} does not fit in 256 bits", value)))
+}
+
+// Normalize a block parameter to its JSON-RPC form.
+// Accepts a block number (as a number, decimal string or hex string) or one of the
+// named tags latest/earliest/pending/safe/finalized. Missing values default to "latest".
+#[wasm_bindgen]
+pub fn normalize_block_tag(tag: &JsValue) -> Result<String, JsValue> {
+    if tag.is_undefined() || tag.is_null() {
+        return Ok("latest".to_string());
+    }
+
+    if let Some(number) = tag.as_f64() {
+        if !number.is_finite() || number < 0.0 || number.fract() != 0.0 || number > 9_007_199_254_740_991.0 {
+            return Err(JsValue::from_str(&format!("Invalid block number: {}", number)));
+        }
+        return Ok(block_number_to_tag(number as u64));
+    }
+
+    let tag_str = tag.as_string()
+        .ok_or_else(|| JsValue::from_str("Block tag must be a number or a string"))?;
+    normalize_block_tag_str(&tag_str).map_err(|e| JsValue::from_str(&e))
+}
+
+// Format a block number as the hex quantity JSON-RPC expects
+pub fn block_number_to_tag(block_number: u64) -> String {
+    format!("0x{:x}", block_number)
+}
+
+fn normalize_block_tag_str(tag: &str) -> Result<String, String> {
+    let trimmed = tag.trim();
+    let lower = trimmed.to_lowercase();
+
+    let block_number = match lower.as_str() {
+        "latest" | "earliest" | "pending" | "safe" | "finalized" => return Ok(lower),
+        _ => match lower.strip_prefix("0x") {
+            Some(hex) if !hex.is_empty() => u64::from_str_radix(hex, 16).ok(),
+            Some(_) => None,
+            None if !lower.is_empty() && lower.chars().all(|c| c.is_ascii_digit()) => lower.parse::<u64>().ok(),
+            None => None,
+        },
+    };
+
+    block_number
+        .map(block_number_to_tag)
+        .ok_or_else(|| format!(
+            "Invalid block tag '{}': expected a block number or one of latest, earliest, pending, safe, finalized",
+            tag
+        ))
+}
+
+// Well-known chains: chain ID, display name and the lowercase aliases accepted by chain_id_for_network
+const KNOWN_NETWORKS: &[(u64, &str, &[&str])] = &[
+    (1, "Ethereum Mainnet", &["mainnet", "ethereum", "homestead"]),
+    (5, "Goerli", &["goerli"]),
+    (11155111, "Sepolia", &["sepolia"]),
+    (137, "Polygon", &["polygon", "matic"]),
+    (56, "BNB Smart Chain", &["bsc", "bnb", "binance"]),
+    (10, "Optimism", &["optimism", "op"]),
+    (42161, "Arbitrum One", &["arbitrum", "arbitrum-one"]),
+    (8453, "Base", &["base"]),
+    (31337, "Localhost", &["localhost", "local", "hardhat", "anvil"]),
+];
+
+// Human-readable name of a chain, or "Unknown (<chainId>)" for chains not in the list
+#[wasm_bindgen]
+pub fn network_name(chain_id: u64) -> String {
+    KNOWN_NETWORKS.iter()
+        .find(|(id, _, _)| *id == chain_id)
+        .map(|(_, name, _)| name.to_string())
+        .unwrap_or_else(|| format!("Unknown ({})", chain_id))
+}
+
+// Chain ID for a network name or alias, ignoring case (e.g. "Sepolia", "mainnet", "bsc")
+#[wasm_bindgen]
+pub fn chain_id_for_network(name: &str) -> Option<u64> {
+    let name = name.trim().to_lowercase();
+    KNOWN_NETWORKS.iter()
+        .find(|(_, display_name, aliases)| display_name.to_lowercase() == name || aliases.contains(&name.as_str()))
+        .map(|(id, _, _)| *id)
+}
+
 // Check if an Ethereum address is valid
 #[wasm_bindgen]
 pub fn is_valid_eth_address(address: &str) -> bool {
@@ -53,6 +263,227 @@ pub fn is_valid_eth_address(address: &str) -> bool {
     hex_part.chars().all(|c| c.is_digit(16))
 }
 
+// Validate an address and return it in checksummed form. Shared by the address-taking
+// entry points so malformed input fails early with "Invalid Ethereum address: {value}".
+pub(crate) fn normalize_address(address: &str) -> Result<String, JsValue> {
+    if !is_valid_eth_address(address) {
+        return Err(RustWasmError::InvalidAddress(address.to_string()).into());
+    }
+    let bytes = hex_to_bytes(address).map_err(|e| JsValue::from_str(&e))?;
+    Ok(checksum_address(&bytes))
+}
+
+// Compare two addresses case-insensitively, so checksummed and lowercase forms match.
+// Returns false if either address is malformed.
+#[wasm_bindgen]
+pub fn addresses_equal(a: &str, b: &str) -> bool {
+    is_valid_eth_address(a) && is_valid_eth_address(b) && a.to_lowercase() == b.to_lowercase()
+}
+
+// Recover the address that signed a message with `personal_sign`.
+// The message is hashed with the Ethereum signed-message prefix and the 65-byte
+// signature (r, s, v with v as 27/28 or 0/1) is run through ecrecover.
+// Returns the signer's checksummed address.
+#[wasm_bindgen]
+pub fn recover_signer(message: String, signature: String) -> Result<String, JsValue> {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    let signature_bytes = hex_to_bytes(&signature).map_err(|e| JsValue::from_str(&format!("Invalid signature: {}", e)))?;
+    if signature_bytes.len() != 65 {
+        return Err(JsValue::from_str(&format!("Invalid signature length: expected 65 bytes, got {}", signature_bytes.len())));
+    }
+
+    let v = match signature_bytes[64] {
+        27 | 28 => signature_bytes[64] - 27,
+        0 | 1 => signature_bytes[64],
+        v => return Err(JsValue::from_str(&format!("Invalid signature recovery id: {}", v))),
+    };
+
+    let mut ecdsa_signature = Signature::from_slice(&signature_bytes[..64])
+        .map_err(|_| JsValue::from_str("Invalid signature: r or s is out of range"))?;
+    let mut recovery_id = RecoveryId::from_byte(v).ok_or_else(|| JsValue::from_str("Invalid signature recovery id"))?;
+
+    // Normalizing a high-s signature flips the parity of the recovered point
+    if let Some(normalized) = ecdsa_signature.normalize_s() {
+        ecdsa_signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+
+    let prefixed = [
+        format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+        message.as_bytes(),
+    ].concat();
+    let message_hash = keccak256(&prefixed);
+
+    let public_key = VerifyingKey::recover_from_prehash(&message_hash, &ecdsa_signature, recovery_id)
+        .map_err(|_| JsValue::from_str("Failed to recover signer from signature"))?;
+
+    // The address is the last 20 bytes of the hash of the uncompressed public key (without the 0x04 tag)
+    let encoded_point = public_key.to_encoded_point(false);
+    let address_hash = keccak256(&encoded_point.as_bytes()[1..]);
+
+    Ok(checksum_address(&address_hash[12..]))
+}
+
+// Predict the address of a contract created with CREATE by `deployer` at account `nonce`:
+// the last 20 bytes of keccak256(rlp([deployer, nonce])), checksummed
+#[wasm_bindgen]
+pub fn compute_contract_address(deployer: &str, nonce: u64) -> Result<String, JsValue> {
+    if !is_valid_eth_address(deployer) {
+        return Err(RustWasmError::InvalidAddress(deployer.to_string()).into());
+    }
+    let deployer_bytes = hex_to_bytes(deployer).map_err(|e| JsValue::from_str(&e))?;
+
+    // RLP encodes integers big-endian without leading zeros, so nonce 0 is the empty string
+    let nonce_bytes: Vec<u8> = nonce.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+
+    let encoded = rlp_encode_list(&[rlp_encode_bytes(&deployer_bytes), rlp_encode_bytes(&nonce_bytes)]);
+    let hash = keccak256(&encoded);
+
+    Ok(checksum_address(&hash[12..]))
+}
+
+// Build the unsigned transaction a signer signs for `chain_id` and return its RLP encoding
+// as hex, for offline or air-gapped signing. With max_fee_per_gas set it is an EIP-1559
+// (type 2) transaction, 0x02 || rlp([chainId, nonce, maxPriorityFee, maxFee, gas, to, value,
+// data, accessList]); otherwise a legacy transaction in its EIP-155 signing form,
+// rlp([nonce, gasPrice, gas, to, value, data, chainId, 0, 0]). The nonce and the fees of the
+// chosen type (max_priority_fee_per_gas, or gas_price for legacy) are required, so a missing
+// field is an error rather than a signable transaction with a zero nonce or fee.
+#[wasm_bindgen]
+pub fn build_unsigned_tx(tx: TransactionData, chain_id: u64) -> Result<String, JsValue> {
+    if !is_valid_eth_address(&tx.to) {
+        return Err(RustWasmError::InvalidAddress(tx.to.clone()).into());
+    }
+    let encoded = encode_unsigned_tx(&tx, chain_id).map_err(|e| JsValue::from_str(&e))?;
+    Ok(bytes_to_hex(&encoded))
+}
+
+fn encode_unsigned_tx(tx: &TransactionData, chain_id: u64) -> Result<Vec<u8>, String> {
+    if let Some(tx_chain_id) = tx.chain_id {
+        if tx_chain_id != chain_id {
+            return Err(format!(
+                "Chain ID mismatch: transaction is for chain {} but {} was requested", tx_chain_id, chain_id
+            ));
+        }
+    }
+
+    let to = hex_to_bytes(&tx.to)?;
+    let value = parse_quantity_str(&tx.value, "value")?;
+    let data = hex_to_bytes(&tx.data)?;
+
+    let nonce = U256::from(tx.nonce.ok_or_else(|| missing_tx_field("nonce"))?);
+    let gas = U256::from(tx.gas);
+    let chain_id = U256::from(chain_id);
+
+    let encoded = match &tx.max_fee_per_gas {
+        Some(max_fee) => {
+            let max_fee = parse_quantity_str(max_fee, "max_fee_per_gas")?;
+            let max_priority_fee = tx.max_priority_fee_per_gas.as_deref()
+                .ok_or_else(|| missing_tx_field("max_priority_fee_per_gas"))
+                .and_then(|fee| parse_quantity_str(fee, "max_priority_fee_per_gas"))?;
+            let fields = rlp_encode_list(&[
+                rlp_encode_u256(chain_id),
+                rlp_encode_u256(nonce),
+                rlp_encode_u256(max_priority_fee),
+                rlp_encode_u256(max_fee),
+                rlp_encode_u256(gas),
+                rlp_encode_bytes(&to),
+                rlp_encode_u256(value),
+                rlp_encode_bytes(&data),
+                // Empty access list
+                rlp_encode_list(&[]),
+            ]);
+            [vec![0x02], fields].concat()
+        },
+        None => {
+            let gas_price = tx.gas_price.as_deref()
+                .ok_or_else(|| missing_tx_field("gas_price"))
+                .and_then(|price| parse_quantity_str(price, "gas_price"))?;
+            rlp_encode_list(&[
+                rlp_encode_u256(nonce),
+                rlp_encode_u256(gas_price),
+                rlp_encode_u256(gas),
+                rlp_encode_bytes(&to),
+                rlp_encode_u256(value),
+                rlp_encode_bytes(&data),
+                rlp_encode_u256(chain_id),
+                rlp_encode_u256(U256::zero()),
+                rlp_encode_u256(U256::zero()),
+            ])
+        },
+    };
+
+    Ok(encoded)
+}
+
+fn missing_tx_field(field: &str) -> String {
+    format!("Transaction {} is required to build an unsigned transaction", field)
+}
+
+// Parse a wei quantity given as a decimal or 0x-prefixed hex string
+pub(crate) fn parse_quantity(value: &str, field: &str) -> Result<U256, JsValue> {
+    parse_quantity_str(value, field).map_err(|e| JsValue::from_str(&e))
+}
+
+fn parse_quantity_str(value: &str, field: &str) -> Result<U256, String> {
+    let parsed = match strip_hex_prefix(value) {
+        Some(digits) if !digits.is_empty() => U256::from_str_radix(digits, 16).ok(),
+        Some(_) => None,
+        None => U256::from_dec_str(value).ok(),
+    };
+    parsed.ok_or_else(|| format!("Invalid {}: '{}'", field, value))
+}
+
+// RLP-encode an integer as its big-endian bytes without leading zeros
+fn rlp_encode_u256(value: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    let first_non_zero = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    rlp_encode_bytes(&bytes[first_non_zero..])
+}
+
+// RLP-encode a byte string: a single byte below 0x80 is its own encoding,
+// anything else gets a length prefix
+fn rlp_encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    [rlp_length_prefix(bytes.len(), 0x80), bytes.to_vec()].concat()
+}
+
+// RLP-encode a list of already-encoded items
+fn rlp_encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    [rlp_length_prefix(payload.len(), 0xc0), payload].concat()
+}
+
+// Short payloads (under 56 bytes) fold their length into the prefix byte;
+// longer ones follow the prefix with the big-endian length
+fn rlp_length_prefix(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![offset + length as u8];
+    }
+    let length_bytes: Vec<u8> = length.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+    [vec![offset + 55 + length_bytes.len() as u8], length_bytes].concat()
+}
+
+// Format a 20-byte address with EIP-55 mixed-case checksum
+pub(crate) fn checksum_address(address: &[u8]) -> String {
+    let lower_hex: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = keccak256(lower_hex.as_bytes());
+
+    let checksummed: String = lower_hex.chars().enumerate()
+        .map(|(i, c)| {
+            // Uppercase a letter when the matching nibble of the hash is 8 or higher
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
+
 // Parse JSON string
 pub fn parse_json(json_str: &str) -> Result<Value, JsonError> {
     serde_json::from_str(json_str)
@@ -77,13 +508,213 @@ pub fn log_warning(message: &str) {
     println!("WARNING: {}", message);
 }
 
-// Calculate gas limit (example function)
+// Percentage added to gas estimates when a gas limit is filled in automatically
+pub const DEFAULT_GAS_BUFFER_PERCENT: u32 = 20;
+
+// Raise a gas estimate by `percent` (0-100), rounding up, e.g. 20 turns 21000 into 25200
 #[wasm_bindgen]
-pub fn estimate_gas_limit(data_size: u32) -> u64 {
-    // Simple example: calculate gas limit based on data size
-    // In real scenarios, more complex calculations may be required
-    let base_gas = 21000; // Base transaction gas
-    let data_gas = data_size as u64 * 68; // Gas per data byte
+pub fn apply_gas_buffer(estimate: u64, percent: u32) -> Result<u64, JsValue> {
+    if percent > 100 {
+        return Err(JsValue::from_str(&format!("Gas buffer must be between 0 and 100 percent, got {}", percent)));
+    }
     
-    base_gas + data_gas
+    let buffered = (estimate as u128 * (100 + percent) as u128).div_ceil(100);
+    u64::try_from(buffered).map_err(|_| JsValue::from_str("Buffered gas limit overflows u64"))
+}
+
+// Number of bytes encoded by a hex string, with or without the 0x prefix.
+// An odd trailing nibble counts as a whole byte.
+#[wasm_bindgen]
+pub fn hex_byte_len(data: &str) -> u32 {
+    let hex = data.strip_prefix("0x").unwrap_or(data);
+    hex.len().div_ceil(2) as u32
+}
+
+// Total cost in wei (decimal string) of `gas_limit` gas at `gas_price_wei`,
+// both given as decimal or 0x-prefixed hex strings
+#[wasm_bindgen]
+pub fn gas_cost(gas_limit: &str, gas_price_wei: &str) -> Result<String, JsValue> {
+    parse_quantity(gas_limit, "gas limit")?
+        .checked_mul(parse_quantity(gas_price_wei, "gas price")?)
+        .map(|cost| cost.to_string())
+        .ok_or_else(|| JsValue::from_str("Gas cost overflows 256 bits"))
+}
+
+// Like `gas_cost`, but formatted in ether, e.g. 100000 gas at 21 gwei gives "0.0021"
+#[wasm_bindgen]
+pub fn gas_cost_in_ether(gas_limit: &str, gas_price_wei: &str) -> Result<String, JsValue> {
+    format_units(&gas_cost(gas_limit, gas_price_wei)?, 18)
+}
+
+// Intrinsic gas of a transaction carrying the given hex calldata:
+// the 21000 base plus EIP-2028 calldata costs of 4 gas per zero byte and 16 per non-zero byte
+#[wasm_bindgen]
+pub fn estimate_gas_limit(data: &str) -> Result<u64, JsValue> {
+    let bytes = hex_to_bytes(data)
+        .map_err(|e| JsValue::from_str(&format!("Invalid calldata: {}", e)))?;
+    
+    let base_gas = 21000;
+    let zero_bytes = bytes.iter().filter(|b| **b == 0).count() as u64;
+    let non_zero_bytes = bytes.len() as u64 - zero_bytes;
+    
+    Ok(base_gas + zero_bytes * 4 + non_zero_bytes * 16)
+}
+
+// Split ABI data into its 32-byte words for inspection, returning `{ selector, words }`.
+// Calldata whose length is 4 bytes past a word boundary has its selector split off;
+// `selector` is null for return data and other word-aligned blobs.
+#[wasm_bindgen]
+pub fn split_words(data: &str) -> Result<JsValue, JsValue> {
+    let (selector, words) = split_abi_words(data).map_err(|e| JsValue::from_str(&e))?;
+    
+    let result = js_sys::Object::new();
+    let selector = selector.map(|s| JsValue::from_str(&s)).unwrap_or(JsValue::NULL);
+    js_sys::Reflect::set(&result, &JsValue::from_str("selector"), &selector)?;
+    let words: js_sys::Array = words.iter().map(|word| JsValue::from_str(word)).collect();
+    js_sys::Reflect::set(&result, &JsValue::from_str("words"), &words)?;
+    
+    Ok(result.into())
+}
+
+fn split_abi_words(data: &str) -> Result<(Option<String>, Vec<String>), String> {
+    let bytes = hex_to_bytes(data)?;
+    
+    let (selector, body) = match bytes.len() % 32 {
+        0 => (None, &bytes[..]),
+        4 => (Some(bytes_to_hex(&bytes[..4])), &bytes[4..]),
+        remainder => return Err(format!(
+            "ABI data is {} bytes, which is neither a multiple of 32 nor a 4-byte selector followed by 32-byte words ({} bytes left over)",
+            bytes.len(), remainder
+        )),
+    };
+    
+    Ok((selector, body.chunks(32).map(bytes_to_hex).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_ether_truncates_or_rounds_to_fixed_decimals() {
+        assert_eq!(format_ether("1234567890000000000", 2, false).unwrap(), "1.23");
+        assert_eq!(format_ether("1234567890000000000", 2, true).unwrap(), "1.23");
+        assert_eq!(format_ether("1235000000000000000", 2, false).unwrap(), "1.23");
+        assert_eq!(format_ether("1235000000000000000", 2, true).unwrap(), "1.24");
+        assert_eq!(format_ether("1995000000000000000", 2, true).unwrap(), "2.00");
+        assert_eq!(format_ether("1500000000000000000", 0, true).unwrap(), "2");
+        assert_eq!(format_ether("0xde0b6b3a7640000", 20, false).unwrap(), "1.00000000000000000000");
+    }
+
+    #[test]
+    fn block_tags_normalize_numbers_and_names() {
+        assert_eq!(block_number_to_tag(0), "0x0");
+        assert_eq!(block_number_to_tag(100), "0x64");
+        assert_eq!(normalize_block_tag_str("100").unwrap(), "0x64");
+        assert_eq!(normalize_block_tag_str("0x64").unwrap(), "0x64");
+        assert_eq!(normalize_block_tag_str("0X0064").unwrap(), "0x64");
+        for tag in ["latest", "earliest", "pending", "safe", "finalized"] {
+            assert_eq!(normalize_block_tag_str(tag).unwrap(), tag);
+            assert_eq!(normalize_block_tag_str(&tag.to_uppercase()).unwrap(), tag);
+        }
+        assert!(normalize_block_tag_str("newest").is_err());
+        assert!(normalize_block_tag_str("0x").is_err());
+        assert!(normalize_block_tag_str("-1").is_err());
+    }
+
+    #[test]
+    fn recover_signer_accepts_both_recovery_id_forms() {
+        // personal_sign("hello world") with the key 0x4c0883a6...3f362318
+        const SIGNATURE: &str = "0x0d5df3f9681b000a5b3a1d4252803318136714deba10c578f9b33718ad9c816e5520a6f19168136aa11e38d691d28a422bce4be3bf290fdb8efe45cdaefb20251c";
+        const SIGNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+        assert_eq!(recover_signer("hello world".to_string(), SIGNATURE.to_string()).unwrap(), SIGNER);
+
+        let raw_recovery_id = format!("{}01", &SIGNATURE[..SIGNATURE.len() - 2]);
+        assert_eq!(recover_signer("hello world".to_string(), raw_recovery_id).unwrap(), SIGNER);
+
+        assert_ne!(recover_signer("hello world!".to_string(), SIGNATURE.to_string()).unwrap(), SIGNER);
+    }
+
+    #[test]
+    fn estimate_gas_limit_prices_zero_and_non_zero_bytes() {
+        assert_eq!(estimate_gas_limit("0x").unwrap(), 21000);
+        assert_eq!(estimate_gas_limit("0x0000").unwrap(), 21000 + 2 * 4);
+        assert_eq!(estimate_gas_limit("0x00ff0001").unwrap(), 21000 + 2 * 4 + 2 * 16);
+        // transfer(address,uint256) selector followed by two words of mostly zero padding
+        let transfer = format!("0xa9059cbb{:0>64}{:0>64}", "1111111111111111111111111111111111111111", "01");
+        assert_eq!(estimate_gas_limit(&transfer).unwrap(), 21000 + 4 * 16 + 21 * 16 + 43 * 4);
+    }
+
+    #[test]
+    fn format_units_handles_sub_unit_and_zero_amounts() {
+        assert_eq!(format_units("1", 18).unwrap(), "0.000000000000000001");
+        assert_eq!(format_units("0", 18).unwrap(), "0");
+        assert_eq!(format_units("0x0", 6).unwrap(), "0");
+        assert_eq!(format_units("1500000", 6).unwrap(), "1.5");
+        assert_eq!(format_units("0xf4240", 6).unwrap(), "1");
+        assert_eq!(format_units("42", 0).unwrap(), "42");
+    }
+
+    #[test]
+    fn compute_contract_address_matches_create_addresses() {
+        const DEPLOYER: &str = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+        assert_eq!(compute_contract_address(DEPLOYER, 0).unwrap().to_lowercase(), "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+        assert_eq!(compute_contract_address(DEPLOYER, 1).unwrap().to_lowercase(), "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8");
+
+        // 127 is the last nonce RLP encodes as a single byte, 128 the first that needs a length prefix
+        let deployer: ethers::types::Address = DEPLOYER.parse().unwrap();
+        for nonce in [0u64, 127, 128, 255, 256, 0x1234_5678, u64::MAX] {
+            let expected = ethers::utils::get_contract_address(deployer, nonce);
+            assert_eq!(compute_contract_address(DEPLOYER, nonce).unwrap(), ethers::utils::to_checksum(&expected, None));
+        }
+    }
+
+    fn unsigned_tx(value: &str, data: &str, nonce: u64) -> TransactionData {
+        let mut tx = TransactionData::new(
+            String::new(), "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string(), value.to_string(), 21000, data.to_string(),
+        );
+        tx.nonce = Some(nonce);
+        tx
+    }
+
+    #[test]
+    fn unsigned_txs_match_ethers_rlp() {
+        use ethers::types::{transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, TransactionRequest};
+
+        const SEPOLIA: u64 = 11155111;
+        let to: ethers::types::Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse().unwrap();
+        for (value, data, nonce) in [("0", "0x", 0u64), ("1000000000000000000", "0xa9059cbb00ff", 0x1234)] {
+            let mut legacy = unsigned_tx(value, data, nonce);
+            legacy.gas_price = Some("20000000000".to_string());
+            let expected = TypedTransaction::Legacy(TransactionRequest::new()
+                .to(to).value(U256::from_dec_str(value).unwrap()).data(hex_to_bytes(data).unwrap())
+                .nonce(nonce).gas(21000).gas_price(20_000_000_000u64).chain_id(SEPOLIA));
+            assert_eq!(encode_unsigned_tx(&legacy, SEPOLIA).unwrap(), expected.rlp().to_vec());
+
+            let mut eip1559 = unsigned_tx(value, data, nonce);
+            eip1559.max_fee_per_gas = Some("0x6fc23ac00".to_string());
+            eip1559.max_priority_fee_per_gas = Some("1000000000".to_string());
+            let expected = TypedTransaction::Eip1559(Eip1559TransactionRequest::new()
+                .to(to).value(U256::from_dec_str(value).unwrap()).data(hex_to_bytes(data).unwrap())
+                .nonce(nonce).gas(21000).max_fee_per_gas(30_000_000_000u64).max_priority_fee_per_gas(1_000_000_000u64)
+                .chain_id(SEPOLIA));
+            assert_eq!(encode_unsigned_tx(&eip1559, SEPOLIA).unwrap(), expected.rlp().to_vec());
+        }
+    }
+
+    #[test]
+    fn unsigned_tx_requires_nonce_and_fees() {
+        let mut tx = unsigned_tx("0", "0x", 0);
+        tx.nonce = None;
+        tx.gas_price = Some("1".to_string());
+        assert_eq!(encode_unsigned_tx(&tx, 1).unwrap_err(), missing_tx_field("nonce"));
+
+        let tx = unsigned_tx("0", "0x", 0);
+        assert_eq!(encode_unsigned_tx(&tx, 1).unwrap_err(), missing_tx_field("gas_price"));
+
+        let mut tx = unsigned_tx("0", "0x", 0);
+        tx.max_fee_per_gas = Some("1".to_string());
+        assert_eq!(encode_unsigned_tx(&tx, 1).unwrap_err(), missing_tx_field("max_priority_fee_per_gas"));
+    }
 }